@@ -0,0 +1,200 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use serde::{Deserialize, Serialize};
+
+use crate::analyze_file::{analyze_file, path_string, syntax};
+use crate::analyzed_module::AnalyzedModule;
+use crate::error::Result;
+use crate::module_symbols::ModuleSymbols;
+
+/// Version of the entries, bump it whenever `ModuleSymbols` or the analysis of
+/// a file changes between releases.
+const SCHEMA_VERSION: u32 = 2;
+
+/// On-disk cache of the symbols of analyzed files, before import paths are
+/// resolved. There is one entry per file, valid as long as the content of the
+/// file, the parser options and the versions of the tool and the entries match.
+#[derive(Debug, Clone)]
+pub struct AnalysisCache {
+    path: PathBuf,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    path: PathBuf,
+    key: String,
+    symbols: ModuleSymbols<String>,
+}
+
+// reads only the path of an entry when evicting
+#[derive(Deserialize)]
+struct CacheEntryPath {
+    path: PathBuf,
+}
+
+// distinguishes temporary files written concurrently
+static TEMP_FILE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+impl AnalysisCache {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Symbols of the file from the cache, the file is analyzed and the cache
+    /// updated on a miss. Failures to read or write the cache are ignored.
+    pub fn analyze_file(&self, path: &Path) -> Result<AnalyzedModule<String>> {
+        let Ok(content) = fs::read(path) else {
+            return analyze_file(path);
+        };
+
+        let canonical_path = path.canonicalize().unwrap_or_else(|_| path.to_owned());
+        let entry_path = self.entry_path(&canonical_path);
+        let key = entry_key(path, &content);
+
+        if let Some(entry) = fs::read(&entry_path)
+            .ok()
+            .and_then(|entry| serde_json::from_slice::<CacheEntry>(&entry).ok())
+            .filter(|entry| entry.key == key)
+        {
+            return Ok(AnalyzedModule::new(path_string(path)?, entry.symbols));
+        }
+
+        let analyzed_module = analyze_file(path)?;
+        self.write_entry(
+            &entry_path,
+            &CacheEntry {
+                path: canonical_path,
+                key,
+                symbols: analyzed_module.symbols.clone(),
+            },
+        );
+        Ok(analyzed_module)
+    }
+
+    /// Remove the entries of files which don't exist anymore.
+    pub fn evict_missing(&self) {
+        let Ok(dir) = fs::read_dir(&self.path) else {
+            return;
+        };
+
+        for entry_path in dir.flatten().map(|e| e.path()) {
+            let is_missing = fs::read(&entry_path)
+                .ok()
+                .and_then(|entry| serde_json::from_slice::<CacheEntryPath>(&entry).ok())
+                .is_none_or(|entry| !entry.path.exists());
+
+            // temporary files are never parsed, those of concurrent runs are left alone
+            if is_missing && entry_path.extension().is_some_and(|e| e == "json") {
+                let _ = fs::remove_file(&entry_path);
+            }
+        }
+    }
+
+    fn entry_path(&self, path: &Path) -> PathBuf {
+        let hash = blake3::hash(path.as_os_str().as_encoded_bytes());
+
+        self.path.join(format!("{}.json", hash.to_hex()))
+    }
+
+    fn write_entry(&self, entry_path: &Path, entry: &CacheEntry) {
+        let Ok(entry) = serde_json::to_vec(entry) else {
+            return;
+        };
+
+        // write to a temporary file first so concurrent runs never read a partial entry
+        let temp_path = entry_path.with_extension(format!(
+            "{}-{}.tmp",
+            std::process::id(),
+            TEMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+
+        let written = fs::create_dir_all(&self.path)
+            .and_then(|_| fs::write(&temp_path, entry))
+            .and_then(|_| fs::rename(&temp_path, entry_path));
+
+        if written.is_err() {
+            let _ = fs::remove_file(&temp_path);
+        }
+    }
+}
+
+/// Everything the symbols of the file depend on.
+fn entry_key(path: &Path, content: &[u8]) -> String {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(env!("CARGO_PKG_VERSION").as_bytes());
+    hasher.update(&[0]);
+    hasher.update(&SCHEMA_VERSION.to_le_bytes());
+    hasher.update(format!("{:?}", syntax(path)).as_bytes());
+    hasher.update(&[0]);
+    hasher.update(content);
+
+    hasher.finalize().to_hex().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use crate::{analysis_cache::AnalysisCache, analyze_file::analyze_file};
+
+    #[test]
+    fn analysis_cache() {
+        let cache_path = std::env::temp_dir().join(format!(
+            "ts-unused-symbols-cache-test-{}",
+            std::process::id()
+        ));
+        let cache = AnalysisCache::new(cache_path.clone());
+        let path = PathBuf::from("./tests/unused-locals/src/helpers.ts");
+
+        let analyzed = cache.analyze_file(&path).unwrap();
+        let entries = fs::read_dir(&cache_path).unwrap().collect::<Vec<_>>();
+        assert_eq!(entries.len(), 1);
+
+        let cached = cache.analyze_file(&path).unwrap();
+        let uncached = analyze_file(&path).unwrap();
+        assert_eq!(cached.symbols.declarations, uncached.symbols.declarations);
+        assert_eq!(cached.symbols.usages, analyzed.symbols.usages);
+        assert_eq!(cached.symbols.imports, uncached.symbols.imports);
+
+        // corrupted entries are replaced
+        let entry_path = entries[0].as_ref().unwrap().path();
+        fs::write(&entry_path, "{").unwrap();
+        let recovered = cache.analyze_file(&path).unwrap();
+        assert_eq!(recovered.symbols.exports, uncached.symbols.exports);
+        assert_ne!(fs::read_to_string(&entry_path).unwrap(), "{");
+
+        fs::remove_dir_all(&cache_path).unwrap();
+    }
+
+    #[test]
+    fn evicted_entries() {
+        let path = std::env::temp_dir().join(format!(
+            "ts-unused-symbols-eviction-test-{}",
+            std::process::id()
+        ));
+        let cache = AnalysisCache::new(path.join("cache"));
+        fs::create_dir_all(path.join("src")).unwrap();
+        let kept = path.join("src/kept.ts");
+        let removed = path.join("src/removed.ts");
+        fs::write(&kept, "export const a = 1;").unwrap();
+        fs::write(&removed, "export const b = 2;").unwrap();
+
+        cache.analyze_file(&kept).unwrap();
+        cache.analyze_file(&removed).unwrap();
+
+        // a changed file replaces its entry
+        fs::write(&kept, "export const a = 1, c = 3;").unwrap();
+        let changed = cache.analyze_file(&kept).unwrap();
+        assert_eq!(changed.symbols.exports.len(), 2);
+        assert_eq!(fs::read_dir(path.join("cache")).unwrap().count(), 2);
+
+        fs::remove_file(&removed).unwrap();
+        cache.evict_missing();
+        assert_eq!(fs::read_dir(path.join("cache")).unwrap().count(), 1);
+        assert_eq!(cache.analyze_file(&kept).unwrap().symbols.exports.len(), 2);
+
+        fs::remove_dir_all(&path).unwrap();
+    }
+}
@@ -1,96 +1,438 @@
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 use std::path::Path;
 
+use swc_common::comments::{CommentKind, Comments, SingleThreadedComments};
 use swc_common::sync::Lrc;
-use swc_common::SourceMap;
+use swc_common::{
+    FileName, Globals, Mark, SourceFile, SourceMap, Span, Spanned, SyntaxContext, GLOBALS,
+};
 use swc_ecma_ast::{
-    Decl, ExportSpecifier, Module, ModuleDecl, ModuleExportName, ModuleItem, Pat, TsModuleName,
+    CallExpr, Callee, Decl, ExportSpecifier, Expr, Lit, Module, ModuleDecl, ModuleExportName,
+    ModuleItem, Pat, Stmt, TsModuleName,
 };
 use swc_ecma_ast::{EsVersion, ImportSpecifier};
-use swc_ecma_parser::{error::Error, parse_file_as_module, Syntax, TsSyntax};
+use swc_ecma_parser::{error::Error as ParseError, parse_file_as_module, Syntax, TsSyntax};
+use swc_ecma_transforms_base::resolver;
+use swc_ecma_visit::{Visit, VisitMutWith, VisitWith};
 
-use crate::analyze_symbols_usage::SymbolsUsageAnalyzer;
+use crate::analyze_symbols_usage::{module_export_name, pat_idents, SymbolsUsageAnalyzer};
 use crate::analyzed_module::AnalyzedModule;
-use crate::module_symbols::{merge_iter, Export, Import, ImportedSymbol, ModuleSymbols};
+use crate::error::{Error, Result};
+use crate::module_symbols::{
+    merge_iter, Export, FindingKind, Import, ImportedSymbol, Location, ModuleReference,
+    ModuleSymbols, Reexport, ReexportKind, Suppression, SuppressionKind, SymbolKind,
+};
+
+const IGNORE_NEXT_LINE: &str = "ts-unused-symbols-ignore-next-line";
+const DISABLE: &str = "ts-unused-symbols-disable";
+
+pub fn analyze_file(path: &Path) -> Result<AnalyzedModule<String>> {
+    let cm: Lrc<SourceMap> = Default::default();
+    let fm = cm.load_file(path).map_err(|source| Error::Io {
+        path: path.to_owned(),
+        source,
+    })?;
+
+    let symbols = analyze_source_file(path, &cm, &fm).map_err(|e| Error::Parse {
+        path: path.to_owned(),
+        message: e.kind().msg().into_owned(),
+    })?;
 
-pub fn analyze_file(path: &Path) -> AnalyzedModule<String> {
+    Ok(AnalyzedModule::new(path_string(path)?, symbols))
+}
+
+/// Analyze the content of a file not saved to disk yet, `None` if it doesn't parse.
+pub fn analyze_source(path: &Path, source: String) -> Option<AnalyzedModule<String>> {
     let cm: Lrc<SourceMap> = Default::default();
-    let fm = cm.load_file(path).expect("failed to load test.js");
-    let ts_config = TsSyntax {
-        tsx: path.ends_with(".tsx"),
+    let fm = cm.new_source_file(Lrc::new(FileName::Real(path.to_owned())), source);
+
+    let symbols = analyze_source_file(path, &cm, &fm).ok()?;
+
+    Some(AnalyzedModule::new(path_string(path).ok()?, symbols))
+}
+
+/// Parser options for the file, `.tsx`, `.jsx` and `.js` files are parsed with JSX.
+pub fn syntax(path: &Path) -> Syntax {
+    Syntax::Typescript(TsSyntax {
+        tsx: path
+            .extension()
+            .is_some_and(|e| e == "tsx" || e == "jsx" || e == "js"),
         ..TsSyntax::default()
-    };
+    })
+}
 
-    let mut recovered_errors: Vec<Error> = Vec::new();
+fn analyze_source_file(
+    path: &Path,
+    cm: &SourceMap,
+    fm: &SourceFile,
+) -> std::result::Result<ModuleSymbols<String>, ParseError> {
+    let mut recovered_errors: Vec<ParseError> = Vec::new();
+    let comments = SingleThreadedComments::default();
 
-    let syntax = Syntax::Typescript(ts_config);
+    let mut module = parse_file_as_module(
+        fm,
+        syntax(path),
+        EsVersion::EsNext,
+        Some(&comments),
+        &mut recovered_errors,
+    )?;
 
-    let module = parse_file_as_module(&fm, syntax, EsVersion::EsNext, None, &mut recovered_errors)
-        .unwrap_or_else(|_| panic!("Failed on {path:?}"));
+    // identifiers referring to top-level bindings get the top-level syntax context
+    let top_level_ctxt = GLOBALS.set(&Globals::new(), || {
+        let top_level_mark = Mark::new();
+        module.visit_mut_with(&mut resolver(Mark::new(), top_level_mark, true));
+        SyntaxContext::empty().apply_mark(top_level_mark)
+    });
 
-    let mut symbols = analyze_module_symbols(module.clone());
+    let mut symbols = analyze_module_symbols(module.clone(), cm);
     let symbol_usage_analyze = SymbolsUsageAnalyzer::new(
         symbols
             .imports
             .iter()
             .flat_map(|import| import.symbols.clone())
             .collect::<HashSet<Import>>(),
+        top_level_ctxt,
     );
+    symbols.references = analyze_module_references(&module, cm);
+    symbols.suppressions = analyze_suppressions(&module, &comments, cm);
+    (symbols.declarations, symbols.top_level_references) =
+        symbol_usage_analyze.analyze_declarations(module.clone(), cm);
     let symbols_usage = symbol_usage_analyze.analyze_symbols_usage(module);
     symbols.usages = symbols_usage;
 
-    AnalyzedModule::new(path.to_str().unwrap().to_string(), symbols)
+    Ok(symbols)
+}
+
+/// Path of an analyzed module, which is kept as a string until imports are
+/// resolved.
+pub(crate) fn path_string(path: &Path) -> Result<String> {
+    path.to_str()
+        .map(str::to_string)
+        .ok_or_else(|| Error::NonUtf8Path(path.to_owned()))
+}
+
+pub fn analyze_module_symbols(module: Module, cm: &SourceMap) -> ModuleSymbols<String> {
+    let type_declarations = type_declarations(&module);
+
+    merge_iter(
+        module
+            .body
+            .into_iter()
+            .map(|item| analyze_module_item(item, &type_declarations, cm)),
+    )
+}
+
+/// Names declared only as types (interfaces, type aliases) at the top level.
+fn type_declarations(module: &Module) -> HashSet<String> {
+    let mut types = HashSet::new();
+    let mut values = HashSet::new();
+
+    for item in &module.body {
+        let decl = match item {
+            ModuleItem::Stmt(Stmt::Decl(decl)) => decl,
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(decl)) => &decl.decl,
+            _ => continue,
+        };
+
+        match decl {
+            Decl::TsInterface(i) => types.insert(i.id.sym.to_string()),
+            Decl::TsTypeAlias(t) => types.insert(t.id.sym.to_string()),
+            Decl::Class(c) => values.insert(c.ident.sym.to_string()),
+            Decl::Fn(f) => values.insert(f.ident.sym.to_string()),
+            Decl::TsEnum(e) => values.insert(e.id.sym.to_string()),
+            _ => false,
+        };
+    }
+
+    types.difference(&values).cloned().collect()
+}
+
+fn location(cm: &SourceMap, span: Span) -> Location {
+    let loc = cm.lookup_char_pos(span.lo);
+
+    Location {
+        line: loc.line,
+        column: loc.col.0 + 1,
+    }
+}
+
+fn analyze_module_references(module: &Module, cm: &SourceMap) -> HashSet<ModuleReference> {
+    let mut dynamic_references = DynamicReferences::default();
+    module.visit_with(&mut dynamic_references);
+
+    module
+        .body
+        .iter()
+        .filter_map(|item| match item {
+            ModuleItem::ModuleDecl(ModuleDecl::Import(decl)) => {
+                Some((decl.src.value.to_string(), decl.span))
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportAll(decl)) => {
+                Some((decl.src.value.to_string(), decl.span))
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(decl)) => decl
+                .src
+                .as_ref()
+                .map(|src| (src.value.to_string(), decl.span)),
+            _ => None,
+        })
+        .chain(dynamic_references.0)
+        .map(|(specifier, span)| ModuleReference {
+            specifier,
+            location: location(cm, span),
+        })
+        .collect()
 }
 
-pub fn analyze_module_symbols(module: Module) -> ModuleSymbols<String> {
-    merge_iter(module.body.into_iter().map(analyze_module_item))
+/// `import("...")` and `require("...")` calls with a literal specifier.
+#[derive(Default)]
+struct DynamicReferences(Vec<(String, Span)>);
+
+impl Visit for DynamicReferences {
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        let is_reference = match &call.callee {
+            Callee::Import(_) => true,
+            Callee::Expr(callee) => matches!(&**callee, Expr::Ident(i) if i.sym == *"require"),
+            Callee::Super(_) => false,
+        };
+
+        if let Some(Expr::Lit(Lit::Str(specifier))) = call.args.first().map(|a| &*a.expr) {
+            if is_reference {
+                self.0.push((specifier.value.to_string(), call.span));
+            }
+        }
+
+        call.visit_children_with(self);
+    }
 }
 
-fn analyze_module_item(module_item: ModuleItem) -> ModuleSymbols<String> {
+/// Suppression comments anywhere in the module and JSDoc tags of top-level statements.
+fn analyze_suppressions(
+    module: &Module,
+    comments: &SingleThreadedComments,
+    cm: &SourceMap,
+) -> HashSet<Suppression> {
+    let mut suppressions = HashSet::new();
+
+    let (leading, trailing) = comments.borrow_all();
+    for comment in leading.values().chain(trailing.values()).flatten() {
+        let location = location(cm, comment.span);
+
+        // line and block comments alike, optionally followed by the reason
+        let (kind, targets) = if let Some(targets) = directive(&comment.text, IGNORE_NEXT_LINE) {
+            (SuppressionKind::NextLine, targets)
+        } else if let Some(targets) = directive(&comment.text, DISABLE) {
+            (SuppressionKind::File, targets)
+        } else {
+            continue;
+        };
+
+        let (first_line, last_line) = match kind {
+            SuppressionKind::NextLine => (location.line + 1, location.line + 1),
+            _ => (1, usize::MAX),
+        };
+
+        suppressions.insert(Suppression {
+            kind,
+            location,
+            first_line,
+            last_line,
+            targets,
+        });
+    }
+
+    for item in &module.body {
+        let span = item.span();
+
+        for comment in comments.get_leading(span.lo).into_iter().flatten() {
+            // JSDoc comments are the block comments starting with `/**`
+            if comment.kind != CommentKind::Block || !comment.text.starts_with('*') {
+                continue;
+            }
+
+            let tags = comment
+                .text
+                .split_whitespace()
+                .filter_map(|word| word.strip_prefix('@'))
+                .filter(|tag| !tag.is_empty());
+
+            for tag in tags {
+                suppressions.insert(Suppression {
+                    kind: SuppressionKind::Tag(tag.to_owned()),
+                    location: location(cm, comment.span),
+                    first_line: location(cm, span).line,
+                    last_line: cm.lookup_char_pos(span.hi).line,
+                    targets: BTreeSet::new(),
+                });
+            }
+        }
+    }
+
+    suppressions
+}
+
+/// Kinds of findings the directive comment targets, the leading finding kind
+/// names after the directive, the rest of the text is the reason.
+fn directive(text: &str, directive: &str) -> Option<BTreeSet<FindingKind>> {
+    let rest = text.trim().strip_prefix(directive)?;
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+
+    Some(
+        rest.split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|word| !word.is_empty())
+            .map_while(FindingKind::from_name)
+            .collect(),
+    )
+}
+
+fn analyze_module_item(
+    module_item: ModuleItem,
+    type_declarations: &HashSet<String>,
+    cm: &SourceMap,
+) -> ModuleSymbols<String> {
     match module_item {
         ModuleItem::Stmt(_) => ModuleSymbols::default(),
-        ModuleItem::ModuleDecl(decl) => analyze_module_decl(decl),
+        ModuleItem::ModuleDecl(decl) => analyze_module_decl(decl, type_declarations, cm),
     }
 }
 
-fn analyze_module_decl(decl: ModuleDecl) -> ModuleSymbols<String> {
+fn analyze_module_decl(
+    decl: ModuleDecl,
+    type_declarations: &HashSet<String>,
+    cm: &SourceMap,
+) -> ModuleSymbols<String> {
     match decl {
-        ModuleDecl::Import(decl) => ModuleSymbols::new_imported_symbol(ImportedSymbol {
-            from: decl.src.value.to_string(),
-            symbols: decl
-                .specifiers
-                .into_iter()
-                .map(analyze_import_specifier)
-                .collect(),
-        }),
+        ModuleDecl::Import(decl) => {
+            // `import { type A, B }` is split into a type-only and a regular import
+            let (type_only, regular): (Vec<_>, Vec<_>) =
+                decl.specifiers.into_iter().partition(|s| {
+                    decl.type_only || matches!(s, ImportSpecifier::Named(s) if s.is_type_only)
+                });
+
+            let imported_symbol = |specifiers: Vec<ImportSpecifier>, type_only| {
+                ModuleSymbols::new_imported_symbol(ImportedSymbol {
+                    from: decl.src.value.to_string(),
+                    symbols: specifiers
+                        .into_iter()
+                        .map(analyze_import_specifier)
+                        .collect(),
+                    type_only,
+                    location: location(cm, decl.span),
+                })
+            };
+
+            if type_only.is_empty() {
+                imported_symbol(regular, false)
+            } else if regular.is_empty() {
+                imported_symbol(type_only, true)
+            } else {
+                imported_symbol(regular, false).merge(imported_symbol(type_only, true))
+            }
+        }
         ModuleDecl::ExportDecl(decl) => analyze_decl(decl.decl),
         ModuleDecl::ExportNamed(decl) => {
-            merge_iter(decl.specifiers.iter().map(analyze_export_specifier))
+            let exports = merge_iter(
+                decl.specifiers
+                    .iter()
+                    .map(|s| analyze_export_specifier(s, decl.type_only, type_declarations)),
+            );
+
+            match &decl.src {
+                Some(src) => exports.merge(ModuleSymbols::new_reexport(Reexport {
+                    from: src.value.to_string(),
+                    kind: ReexportKind::Named(
+                        decl.specifiers
+                            .iter()
+                            .map(|s| (reexported_name(s), exported_name(s)))
+                            .collect(),
+                    ),
+                    location: location(cm, decl.span),
+                })),
+                None => exports,
+            }
         }
         ModuleDecl::ExportDefaultDecl(_) => ModuleSymbols::new_export(Export::Default),
         ModuleDecl::ExportDefaultExpr(_) => ModuleSymbols::new_export(Export::Default),
-        ModuleDecl::ExportAll(decl) => ModuleSymbols::new_all_export(decl.src.value.to_string()),
+        ModuleDecl::ExportAll(decl) => ModuleSymbols::new_all_export(decl.src.value.to_string())
+            .merge(ModuleSymbols::new_reexport(Reexport {
+                from: decl.src.value.to_string(),
+                kind: ReexportKind::All,
+                location: location(cm, decl.span),
+            })),
         ModuleDecl::TsImportEquals(_) => ModuleSymbols::default(), // TODO
-        ModuleDecl::TsExportAssignment(_) => unimplemented!(),
-        ModuleDecl::TsNamespaceExport(_) => unimplemented!(),
+        // export = <expr>; is imported as the default export
+        ModuleDecl::TsExportAssignment(_) => ModuleSymbols::new_export(Export::Default),
+        // export as namespace <name>; only declares a UMD global
+        ModuleDecl::TsNamespaceExport(_) => ModuleSymbols::default(),
     }
 }
 
-fn analyze_export_specifier(decl: &ExportSpecifier) -> ModuleSymbols<String> {
+fn analyze_export_specifier(
+    decl: &ExportSpecifier,
+    type_only: bool,
+    type_declarations: &HashSet<String>,
+) -> ModuleSymbols<String> {
     match decl {
-        ExportSpecifier::Named(decl) => ModuleSymbols::new_exported_symbol_str(
-            match decl.exported.clone().unwrap_or_else(|| decl.orig.clone()) {
-                ModuleExportName::Str(s) => s.value.to_string(),
-                ModuleExportName::Ident(s) => s.sym.to_string(),
-            },
-        ),
-        i => todo!("{:#?}", i),
+        ExportSpecifier::Named(decl) => {
+            let is_type = type_only
+                || decl.is_type_only
+                || matches!(&decl.orig, ModuleExportName::Ident(i) if type_declarations.contains(&*i.sym));
+
+            let exported = module_export_name(decl.exported.as_ref().unwrap_or(&decl.orig));
+
+            if exported == "default" {
+                // export { <local> as default };
+                return ModuleSymbols::new_export(Export::Default);
+            }
+
+            ModuleSymbols::new_exported_symbol_str(
+                exported,
+                if is_type {
+                    SymbolKind::Type
+                } else {
+                    SymbolKind::Value
+                },
+            )
+        }
+        // export * as <name> from <from>; export <name> from <from>;
+        ExportSpecifier::Namespace(_) | ExportSpecifier::Default(_) => {
+            ModuleSymbols::new_exported_symbol_str(exported_name(decl), SymbolKind::Value)
+        }
     }
 }
 
+fn exported_name(decl: &ExportSpecifier) -> String {
+    match decl {
+        ExportSpecifier::Named(s) => module_export_name(s.exported.as_ref().unwrap_or(&s.orig)),
+        ExportSpecifier::Namespace(s) => module_export_name(&s.name),
+        ExportSpecifier::Default(s) => s.exported.sym.to_string(),
+    }
+}
+
+/// Name the re-exported binding has in the module it comes from, `*` for
+/// `export * as <name> from`.
+fn reexported_name(decl: &ExportSpecifier) -> String {
+    match decl {
+        ExportSpecifier::Named(s) => module_export_name(&s.orig),
+        ExportSpecifier::Namespace(_) => "*".to_string(),
+        ExportSpecifier::Default(_) => "default".to_string(),
+    }
+}
+
+/// Named imports keep the name the exporting module uses besides the local
+/// binding, `import { a as b }` uses the export `a` and declaration maps look
+/// the symbol up by it.
 fn analyze_import_specifier(decl: ImportSpecifier) -> Import {
     match decl {
-        ImportSpecifier::Named(i) => Import::Named(i.local.sym.to_string()),
+        ImportSpecifier::Named(i) => Import::Named(
+            i.imported
+                .as_ref()
+                .map(module_export_name)
+                .unwrap_or_else(|| i.local.sym.to_string()),
+            i.local.sym.to_string(),
+        ),
         ImportSpecifier::Default(i) => Import::Default(i.local.sym.to_string()),
         ImportSpecifier::Namespace(i) => Import::Namespace(i.local.sym.to_string()),
     }
@@ -99,45 +441,56 @@ fn analyze_import_specifier(decl: ImportSpecifier) -> Import {
 fn analyze_decl(decl: Decl) -> ModuleSymbols<String> {
     match decl {
         Decl::Class(class) => {
-            ModuleSymbols::new_exported_symbol(class.ident) // .merge(analyze_class(*class.class))
+            ModuleSymbols::new_exported_symbol(class.ident, SymbolKind::Value) // .merge(analyze_class(*class.class))
         }
-        Decl::Fn(fun) => ModuleSymbols::new_exported_symbol(fun.ident),
+        Decl::Fn(fun) => ModuleSymbols::new_exported_symbol(fun.ident, SymbolKind::Value),
         Decl::Var(var) => merge_iter(var.decls.into_iter().map(|decl| analyze_pattern(decl.name))),
-        Decl::TsEnum(e) => ModuleSymbols::new_exported_symbol(e.id),
-        Decl::TsInterface(i) => ModuleSymbols::new_exported_symbol(i.id),
-        Decl::TsTypeAlias(t) => ModuleSymbols::new_exported_symbol(t.id),
+        Decl::TsEnum(e) => ModuleSymbols::new_exported_symbol(e.id, SymbolKind::Value),
+        Decl::TsInterface(i) => ModuleSymbols::new_exported_symbol(i.id, SymbolKind::Type),
+        Decl::TsTypeAlias(t) => ModuleSymbols::new_exported_symbol(t.id, SymbolKind::Type),
         Decl::TsModule(m) => match m.id {
             TsModuleName::Str(_) => ModuleSymbols::default(),
-            TsModuleName::Ident(i) => ModuleSymbols::new_exported_symbol(i),
+            TsModuleName::Ident(i) => ModuleSymbols::new_exported_symbol(i, SymbolKind::Value),
         },
-        Decl::Using(_) => todo!("implement Decl::Using"),
+        Decl::Using(using) => merge_iter(
+            using
+                .decls
+                .into_iter()
+                .map(|decl| analyze_pattern(decl.name)),
+        ),
     }
 }
 
 fn analyze_pattern(pat: Pat) -> ModuleSymbols<String> {
-    match pat {
-        Pat::Ident(i) => ModuleSymbols::new_exported_symbol(i.id),
-        _ => unimplemented!(),
-    }
+    // export const { a, b: [c] } = o;
+    merge_iter(
+        pat_idents(pat)
+            .into_iter()
+            .map(|i| ModuleSymbols::new_exported_symbol(i, SymbolKind::Value)),
+    )
 }
 
 #[cfg(test)]
 mod tests {
-    use std::{collections::HashSet, path::PathBuf};
+    use std::{collections::HashSet, env::temp_dir, fs, path::PathBuf};
 
     use crate::{
         analyze_file::analyze_file,
-        module_symbols::{Export, Import, ImportedSymbol},
+        error::Error,
+        module_symbols::{Export, Import, ImportedSymbol, Location, ReexportKind, SymbolKind},
     };
 
     #[test]
     fn namespace_imports() {
-        let analyzed_module = analyze_file(&PathBuf::from("./tests/namespace-imports/src/app.ts"));
+        let analyzed_module =
+            analyze_file(&PathBuf::from("./tests/namespace-imports/src/app.ts")).unwrap();
         assert_eq!(
             analyzed_module.symbols.imports,
             HashSet::from([ImportedSymbol {
                 from: "./another".to_string(),
-                symbols: vec![Import::Namespace("A".to_string())]
+                symbols: vec![Import::Namespace("A".to_string())],
+                type_only: false,
+                location: Location { line: 1, column: 1 },
             }])
         );
     }
@@ -146,10 +499,60 @@ mod tests {
     fn reexported_symbols() {
         let analyzed_module = analyze_file(&PathBuf::from(
             "./tests/reexported-symbols/src/sub-module/index.ts",
-        ));
+        ))
+        .unwrap();
         assert_eq!(
             analyzed_module.symbols.exports,
             HashSet::from([Export::AllFrom("./module".to_string())])
         );
     }
+
+    #[test]
+    fn export_forms() {
+        let exports = |file: &str| {
+            analyze_file(&PathBuf::from("./tests/export-forms/src").join(file))
+                .unwrap()
+                .symbols
+                .exports
+        };
+        let value = |name: &str| Export::Symbol(name.to_string(), SymbolKind::Value);
+
+        assert_eq!(
+            exports("values.ts"),
+            HashSet::from([value("a"), value("b"), value("rest")])
+        );
+        assert_eq!(exports("assignment.ts"), HashSet::from([Export::Default]));
+        assert_eq!(exports("app.ts"), HashSet::new());
+
+        let barrel = analyze_file(&PathBuf::from("./tests/export-forms/src/barrel.ts")).unwrap();
+        assert_eq!(barrel.symbols.exports, HashSet::from([value("values")]));
+        assert_eq!(
+            barrel
+                .symbols
+                .reexports
+                .into_iter()
+                .map(|r| r.kind)
+                .collect::<Vec<_>>(),
+            vec![ReexportKind::Named(vec![(
+                "*".to_string(),
+                "values".to_string()
+            )])]
+        );
+    }
+
+    #[test]
+    fn unreadable_and_invalid_files() {
+        let missing = PathBuf::from("./tests/export-forms/src/missing.ts");
+        assert!(matches!(analyze_file(&missing), Err(Error::Io { path, .. }) if path == missing));
+
+        let invalid = temp_dir().join(format!(
+            "ts-unused-symbols-invalid-{}.ts",
+            std::process::id()
+        ));
+        fs::write(&invalid, "export const = ;").unwrap();
+        let result = analyze_file(&invalid);
+        fs::remove_file(&invalid).unwrap();
+
+        assert!(matches!(result, Err(Error::Parse { path, .. }) if path == invalid));
+    }
 }
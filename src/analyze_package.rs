@@ -1,99 +1,417 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt::Debug;
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use anyhow::Result;
+use rayon::prelude::*;
 use regex::Regex;
 
+use crate::analysis_cache::AnalysisCache;
 use crate::analyze_file::analyze_file;
 use crate::analyze_plan::Package;
 use crate::analyzed_module::AnalyzedModule;
-use crate::module_symbols::{Export, ImportedSymbol, ModuleSymbols};
-use crate::resolve_import_path::resolve_import_path;
+use crate::declaration_map::{try_load_declaration_map, DeclarationMap};
+use crate::error::{Error, Result};
+use crate::module_symbols::{Export, Import, ImportedSymbol, ModuleSymbols, Reexport};
+use crate::resolve_import_path::{
+    classify_import, resolve_import_path, ImportKind, POSSIBLE_EXTENSIONS,
+};
 use crate::tsconfig::TsConfig;
 
+/// Import specifiers by the kind of module they resolve to.
+pub type ImportsByKind = HashMap<ImportKind, HashSet<String>>;
+
 #[derive(Clone, Debug)]
 pub struct AnalyzedPackage {
     pub path: PathBuf,
     pub modules: HashMap<PathBuf, AnalyzedModule<PathBuf>>,
     pub unresolved_paths: HashSet<String>,
+    pub imports_by_kind: ImportsByKind,
+    pub entry_points: HashSet<PathBuf>,
+
+    // files failing to parse with the message of the parser, left out of the analysis
+    pub unparsed_files: BTreeMap<PathBuf, String>,
+
+    // import specifiers of each module by kind, merged into `imports_by_kind`
+    module_imports_by_kind: HashMap<PathBuf, ImportsByKind>,
 }
 
-#[derive(Debug, Default)]
+impl AnalyzedPackage {
+    /// Import specifiers of each module by kind.
+    pub fn module_imports_by_kind(&self) -> &HashMap<PathBuf, ImportsByKind> {
+        &self.module_imports_by_kind
+    }
+
+    /// Merge the import specifiers of the modules after some of them changed.
+    fn merge_imports_by_kind(&mut self) {
+        self.imports_by_kind = HashMap::new();
+
+        for kinds in self.module_imports_by_kind.values() {
+            for (kind, imports) in kinds {
+                self.imports_by_kind
+                    .entry(*kind)
+                    .or_default()
+                    .extend(imports.iter().cloned());
+            }
+        }
+
+        self.unresolved_paths = self
+            .imports_by_kind
+            .get(&ImportKind::Unresolved)
+            .cloned()
+            .unwrap_or_default();
+    }
+}
+
+#[derive(Debug)]
 pub struct AnalyzeOptions {
-    pub ignore_patterns: Vec<Regex>,
-    pub exclude_patterns: Vec<Regex>,
+    pub(crate) ignore_patterns: Vec<Regex>,
+    pub(crate) exclude_patterns: Vec<Regex>,
+    pub(crate) cache: Option<AnalysisCache>,
+
+    // JSDoc tags marking exports as used
+    pub(crate) suppression_tags: Vec<String>,
 }
 
-impl AnalyzeOptions {
-    pub fn new(ignore_patterns: Vec<Regex>, exclude_patterns: Vec<Regex>) -> Self {
+impl Default for AnalyzeOptions {
+    fn default() -> Self {
         Self {
-            ignore_patterns,
-            exclude_patterns,
+            ignore_patterns: vec![],
+            exclude_patterns: vec![],
+            cache: None,
+            suppression_tags: vec!["public".to_string(), "api".to_string()],
         }
     }
 }
 
+impl AnalyzeOptions {
+    pub fn builder() -> AnalyzeOptionsBuilder {
+        AnalyzeOptionsBuilder::default()
+    }
+
+    pub fn ignore_patterns(&self) -> &[Regex] {
+        &self.ignore_patterns
+    }
+
+    pub fn exclude_patterns(&self) -> &[Regex] {
+        &self.exclude_patterns
+    }
+
+    pub fn cache(&self) -> Option<&AnalysisCache> {
+        self.cache.as_ref()
+    }
+
+    pub fn suppression_tags(&self) -> &[String] {
+        &self.suppression_tags
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct AnalyzeOptionsBuilder {
+    options: AnalyzeOptions,
+}
+
+impl AnalyzeOptionsBuilder {
+    /// Unused exports of files matching any of the patterns are not reported.
+    pub fn ignore_patterns(mut self, ignore_patterns: Vec<Regex>) -> Self {
+        self.options.ignore_patterns = ignore_patterns;
+        self
+    }
+
+    /// Files matching any of the patterns are not analyzed at all.
+    pub fn exclude_patterns(mut self, exclude_patterns: Vec<Regex>) -> Self {
+        self.options.exclude_patterns = exclude_patterns;
+        self
+    }
+
+    /// Reuse the symbols of files analyzed in the previous runs.
+    pub fn cache(mut self, cache: AnalysisCache) -> Self {
+        self.options.cache = Some(cache);
+        self
+    }
+
+    /// JSDoc tags marking exports as used, `@public` and `@api` by default.
+    pub fn suppression_tags(mut self, suppression_tags: Vec<String>) -> Self {
+        self.options.suppression_tags = suppression_tags;
+        self
+    }
+
+    pub fn build(self) -> AnalyzeOptions {
+        self.options
+    }
+}
+
+pub fn analyze_packages(
+    packages: &[Package],
+    options: &AnalyzeOptions,
+) -> Result<Vec<AnalyzedPackage>> {
+    let analyzed_packages = packages
+        .par_iter()
+        .map(|package| analyze_package(&package.path, &package.tsconfig, options, packages))
+        .collect::<Result<Vec<_>>>()?;
+
+    if let Some(cache) = &options.cache {
+        cache.evict_missing();
+    }
+
+    Ok(analyzed_packages)
+}
+
 pub fn analyze_package(
     path: &Path,
     tsconfig: &Option<TsConfig>,
     options: &AnalyzeOptions,
     packages: &[Package],
 ) -> Result<AnalyzedPackage> {
-    let build_path = tsconfig
+    let build_path = build_path(path, tsconfig);
+
+    let mut declaration_maps = HashMap::new();
+
+    let paths = traverse_path(path, &options.exclude_patterns, &build_path)?;
+
+    // parsing and usage analysis are independent per file, import resolution
+    // shares caches and runs afterwards
+    let analyzed_files = paths
+        .par_iter()
+        .map(|p| analyze_source_file(p, options))
+        .collect::<Vec<_>>();
+
+    let mut analyzed_package = AnalyzedPackage {
+        path: path.to_owned(),
+        modules: HashMap::new(),
+        unresolved_paths: HashSet::new(),
+        imports_by_kind: HashMap::new(),
+        entry_points: packages
+            .iter()
+            .find(|p| p.path == path)
+            .map(|p| p.entry_points.iter().cloned().collect())
+            .unwrap_or_default(),
+        unparsed_files: BTreeMap::new(),
+        module_imports_by_kind: HashMap::new(),
+    };
+
+    for (p, analyzed_file) in paths.into_iter().zip(analyzed_files) {
+        let analyzed_file = match analyzed_file {
+            Ok(analyzed_file) => analyzed_file,
+            Err(Error::Parse { message, .. }) => {
+                analyzed_package.unparsed_files.insert(p, message);
+                continue;
+            }
+            Err(error) => return Err(error),
+        };
+
+        let (module, imports_by_kind) = analyze_module_with_path_resolve(
+            &p,
+            analyzed_file,
+            tsconfig,
+            path,
+            packages,
+            &mut declaration_maps,
+        )?;
+
+        analyzed_package.modules.insert(p.to_owned(), module);
+        analyzed_package
+            .module_imports_by_kind
+            .insert(p, imports_by_kind);
+    }
+
+    analyzed_package.merge_imports_by_kind();
+
+    Ok(analyzed_package)
+}
+
+/// Analyze the modules at the given paths again, dropping the ones which no
+/// longer exist or aren't source files of the package anymore.
+pub fn reanalyze_modules(
+    analyzed_package: &mut AnalyzedPackage,
+    paths: &HashSet<PathBuf>,
+    tsconfig: &Option<TsConfig>,
+    options: &AnalyzeOptions,
+    packages: &[Package],
+) -> Result<()> {
+    let build_path = build_path(&analyzed_package.path, tsconfig);
+    let mut declaration_maps = HashMap::new();
+
+    let (existing, removed): (Vec<&PathBuf>, Vec<&PathBuf>) = paths
+        .iter()
+        .partition(|p| p.is_file() && is_source_file(p, &options.exclude_patterns, &build_path));
+
+    for path in removed {
+        analyzed_package.modules.remove(path);
+        analyzed_package.module_imports_by_kind.remove(path);
+        analyzed_package.unparsed_files.remove(path);
+    }
+
+    let analyzed_files = existing
+        .par_iter()
+        .map(|p| analyze_source_file(p, options))
+        .collect::<Vec<_>>();
+
+    for (path, analyzed_file) in existing.into_iter().zip(analyzed_files) {
+        // the previous analysis of the module is kept until it parses again
+        let analyzed_file = match analyzed_file {
+            Ok(analyzed_file) => analyzed_file,
+            Err(Error::Parse { message, .. }) => {
+                analyzed_package
+                    .unparsed_files
+                    .insert(path.to_owned(), message);
+                continue;
+            }
+            Err(error) => return Err(error),
+        };
+
+        analyzed_package.unparsed_files.remove(path);
+        resolve_module(
+            analyzed_package,
+            path,
+            analyzed_file,
+            tsconfig,
+            packages,
+            &mut declaration_maps,
+        )?;
+    }
+
+    analyzed_package.merge_imports_by_kind();
+
+    Ok(())
+}
+
+/// Replace the module at the path with the analyzed file, resolving its imports.
+pub fn update_module(
+    analyzed_package: &mut AnalyzedPackage,
+    path: &Path,
+    analyzed_file: AnalyzedModule<String>,
+    tsconfig: &Option<TsConfig>,
+    packages: &[Package],
+) -> Result<()> {
+    resolve_module(
+        analyzed_package,
+        path,
+        analyzed_file,
+        tsconfig,
+        packages,
+        &mut HashMap::new(),
+    )?;
+
+    analyzed_package.merge_imports_by_kind();
+
+    Ok(())
+}
+
+fn resolve_module(
+    analyzed_package: &mut AnalyzedPackage,
+    path: &Path,
+    analyzed_file: AnalyzedModule<String>,
+    tsconfig: &Option<TsConfig>,
+    packages: &[Package],
+    declaration_maps: &mut HashMap<PathBuf, Option<DeclarationMap>>,
+) -> Result<()> {
+    let (module, imports_by_kind) = analyze_module_with_path_resolve(
+        path,
+        analyzed_file,
+        tsconfig,
+        &analyzed_package.path,
+        packages,
+        declaration_maps,
+    )?;
+
+    analyzed_package.modules.insert(path.to_owned(), module);
+    analyzed_package
+        .module_imports_by_kind
+        .insert(path.to_owned(), imports_by_kind);
+
+    Ok(())
+}
+
+/// Index of the package with the closest root containing the path.
+pub fn package_of(analyzed_packages: &[AnalyzedPackage], path: &Path) -> Option<usize> {
+    analyzed_packages
+        .iter()
+        .enumerate()
+        .map(|(index, p)| {
+            let root = p.path.canonicalize().unwrap_or_else(|_| p.path.to_owned());
+            (index, root)
+        })
+        .filter(|(_, root)| path.starts_with(root))
+        .max_by_key(|(_, root)| root.components().count())
+        .map(|(index, _)| index)
+}
+
+/// The tsconfig `outDir` of the package, the build output isn't part of the sources.
+pub fn build_path(path: &Path, tsconfig: &Option<TsConfig>) -> Option<PathBuf> {
+    tsconfig
         .clone()
         .and_then(|c| c.compiler_options)
         .and_then(|c| c.out_dir)
         .map(|c| {
-            let mut path = path.to_owned();
-            path.push(c);
-            path.canonicalize().unwrap()
-        });
-
-    let mut unresolved_paths = HashSet::new();
-
-    let paths = traverse_path(path, &options.exclude_patterns, &build_path);
-    let modules = paths
-        .into_iter()
-        .map(|p| {
-            analyze_module_with_path_resolve(&p, tsconfig, path, packages, &mut unresolved_paths)
-                .map(|m| (p.to_owned(), m))
+            // not built yet
+            let path = path.join(c);
+            path.canonicalize().unwrap_or(path)
         })
-        .collect::<Result<_>>()?;
+}
 
-    Ok(AnalyzedPackage {
-        path: path.to_owned(),
-        modules,
-        unresolved_paths,
-    })
+fn analyze_source_file(path: &Path, options: &AnalyzeOptions) -> Result<AnalyzedModule<String>> {
+    match &options.cache {
+        Some(cache) => cache.analyze_file(path),
+        None => analyze_file(path),
+    }
 }
 
 fn analyze_module_with_path_resolve(
     path: &Path,
+    analyzed_file: AnalyzedModule<String>,
     tsconfig: &Option<TsConfig>,
     package_path: &Path,
     packages: &[Package],
-    unresolved_paths: &mut HashSet<String>,
-) -> Result<AnalyzedModule<PathBuf>> {
-    let analyzed_file = analyze_file(path);
+    declaration_maps: &mut HashMap<PathBuf, Option<DeclarationMap>>,
+) -> Result<(AnalyzedModule<PathBuf>, ImportsByKind)> {
+    let mut imports_by_kind = ImportsByKind::new();
+    let mut resolved_paths = HashMap::new();
+
+    for reference in &analyzed_file.symbols.references {
+        if resolved_paths.contains_key(&reference.specifier) {
+            continue;
+        }
+
+        let resolved_import_path =
+            resolve_import_path(path, &reference.specifier, tsconfig, package_path, packages)?;
+        let kind = classify_import(
+            path,
+            &reference.specifier,
+            resolved_import_path.as_deref(),
+            packages,
+        );
+
+        imports_by_kind
+            .entry(kind)
+            .or_default()
+            .insert(reference.specifier.to_owned());
+        resolved_paths.insert(reference.specifier.to_owned(), resolved_import_path);
+    }
+
+    // imports and reexports are among the references, resolved above
+    let resolve = |specifier: &str| match resolved_paths.get(specifier) {
+        Some(resolved_import_path) => Ok(resolved_import_path.clone()),
+        None => resolve_import_path(path, specifier, tsconfig, package_path, packages),
+    };
 
     let exports = analyzed_file
         .symbols
         .exports
         .iter()
         .map(|export| match export {
-            Export::Default => Ok(Some(Export::Default)),
-            Export::Symbol(s) => Ok(Some(Export::Symbol(s.to_owned()))),
+            Export::Default => Ok(vec![Export::Default]),
+            Export::Symbol(s, kind) => Ok(vec![Export::Symbol(s.to_owned(), *kind)]),
             Export::AllFrom(s) => {
-                let resolved_import_path =
-                    resolve_import_path(path, s, tsconfig, package_path, packages);
-
-                if resolved_import_path.as_ref().unwrap_or(&None).is_none() {
-                    unresolved_paths.insert(s.to_owned());
-                }
-
-                resolved_import_path.map(|i| i.map(Export::AllFrom))
+                let resolved_import_path = resolve(s);
+
+                Ok(resolved_import_path?
+                    .map(|from| sources_of_declaration(from, declaration_maps))
+                    .transpose()?
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(Export::AllFrom)
+                    .collect())
             }
         })
         .collect::<Result<Vec<_>>>()?
@@ -106,91 +424,203 @@ fn analyze_module_with_path_resolve(
         .imports
         .iter()
         .map(|import| {
-            let resolved_import_path =
-                resolve_import_path(path, &import.from, tsconfig, package_path, packages);
-
-            if resolved_import_path.as_ref().unwrap_or(&None).is_none() {
-                unresolved_paths.insert(import.from.to_owned());
-            }
+            let resolved_import_path = resolve(&import.from);
+
+            Ok(resolved_import_path?
+                .map(|from| {
+                    split_by_declaration_map(
+                        ImportedSymbol {
+                            symbols: import.symbols.clone(),
+                            from,
+                            type_only: import.type_only,
+                            location: import.location,
+                        },
+                        declaration_maps,
+                    )
+                })
+                .transpose()?
+                .unwrap_or_default())
+        })
+        .collect::<Result<Vec<_>>>()?
+        .into_iter()
+        .flatten()
+        .collect();
 
-            resolved_import_path.map(|from| {
-                from.map(|from| ImportedSymbol {
-                    symbols: import.symbols.clone(),
+    let reexports = analyzed_file
+        .symbols
+        .reexports
+        .iter()
+        .map(|reexport| {
+            let resolved_import_path = resolve(&reexport.from);
+
+            // reexported names can't be told apart once renamed, so a reexport
+            // of a bundled declaration points at all of its sources
+            Ok(resolved_import_path?
+                .map(|from| sources_of_declaration(from, declaration_maps))
+                .transpose()?
+                .unwrap_or_default()
+                .into_iter()
+                .map(|from| Reexport {
                     from,
+                    kind: reexport.kind.clone(),
+                    location: reexport.location,
                 })
-            })
+                .collect::<Vec<_>>())
         })
         .collect::<Result<Vec<_>>>()?
         .into_iter()
         .flatten()
         .collect();
 
-    Ok(AnalyzedModule {
-        path: path.canonicalize().unwrap(),
+    let module = AnalyzedModule {
+        path: path.canonicalize().map_err(|source| Error::Io {
+            path: path.to_owned(),
+            source,
+        })?,
         symbols: ModuleSymbols {
             usages: analyzed_file.symbols.usages,
             exports,
             imports,
+            references: analyzed_file.symbols.references,
+            declarations: analyzed_file.symbols.declarations,
+            reexports,
+            suppressions: analyzed_file.symbols.suppressions,
+            top_level_references: analyzed_file.symbols.top_level_references,
         },
+    };
+
+    Ok((module, imports_by_kind))
+}
+
+fn load_declaration_map<'a>(
+    path: &Path,
+    declaration_maps: &'a mut HashMap<PathBuf, Option<DeclarationMap>>,
+) -> Result<Option<&'a DeclarationMap>> {
+    if !declaration_maps.contains_key(path) {
+        let declaration_map = try_load_declaration_map(path)?;
+        declaration_maps.insert(path.to_owned(), declaration_map);
+    }
+
+    Ok(declaration_maps[path].as_ref())
+}
+
+/// Sources of a declaration file bundled from multiple sources, the path
+/// itself for any other file.
+fn sources_of_declaration(
+    path: PathBuf,
+    declaration_maps: &mut HashMap<PathBuf, Option<DeclarationMap>>,
+) -> Result<Vec<PathBuf>> {
+    Ok(match load_declaration_map(&path, declaration_maps)? {
+        Some(declaration_map) => declaration_map.sources.clone(),
+        None => vec![path],
     })
 }
 
+/// Imports of a declaration file bundled from multiple sources are
+/// redirected to the sources declaring the imported symbols.
+fn split_by_declaration_map(
+    imported_symbol: ImportedSymbol<PathBuf>,
+    declaration_maps: &mut HashMap<PathBuf, Option<DeclarationMap>>,
+) -> Result<Vec<ImportedSymbol<PathBuf>>> {
+    let Some(declaration_map) = load_declaration_map(&imported_symbol.from, declaration_maps)?
+    else {
+        return Ok(vec![imported_symbol]);
+    };
+
+    let mut symbols_by_source: HashMap<PathBuf, Vec<Import>> = HashMap::new();
+
+    for symbol in imported_symbol.symbols {
+        let sources = match &symbol {
+            Import::Named(s, _) => declaration_map.sources_of(s),
+            Import::Default(_) => declaration_map.sources_of("default"),
+            Import::Namespace(_) => declaration_map.sources.clone(),
+        };
+
+        for source in sources {
+            symbols_by_source
+                .entry(source)
+                .or_default()
+                .push(symbol.clone());
+        }
+    }
+
+    Ok(symbols_by_source
+        .into_iter()
+        .map(|(from, symbols)| ImportedSymbol {
+            symbols,
+            from,
+            type_only: imported_symbol.type_only,
+            location: imported_symbol.location,
+        })
+        .collect())
+}
+
+pub fn is_source_file(path: &Path, exclude_patterns: &[Regex], out_dir: &Option<PathBuf>) -> bool {
+    let extension = path.extension().and_then(|s| s.to_str()).unwrap_or("");
+
+    let path_str = path.to_string_lossy();
+
+    POSSIBLE_EXTENSIONS.contains(&extension)
+        && !exclude_patterns.iter().any(|r| r.is_match(&path_str))
+        && !out_dir.as_ref().is_some_and(|o| path.starts_with(o))
+}
+
 fn traverse_path(
     path: &Path,
     exclude_patterns: &[Regex],
     out_dir: &Option<PathBuf>,
-) -> Vec<PathBuf> {
+) -> Result<Vec<PathBuf>> {
+    let io_error = |path: &Path| {
+        let path = path.to_owned();
+        move |source| Error::Io { path, source }
+    };
+
     let mut result = vec![];
-    let dir = fs::read_dir(path).unwrap();
+    let dir = fs::read_dir(path).map_err(io_error(path))?;
 
     for file in dir {
-        let file = file.unwrap();
+        let file = file.map_err(io_error(path))?;
+        let file_path = file.path();
 
-        let file_type = file.file_type().unwrap();
-        let path = file.path().canonicalize().unwrap();
+        let file_type = file.file_type().map_err(io_error(&file_path))?;
+        let path = file_path.canonicalize().map_err(io_error(&file_path))?;
 
         if out_dir.as_ref().map(|i| i == &path).unwrap_or(false) {
             continue;
         }
 
-        let path_str = path.to_str().unwrap();
-
         if file_type.is_dir() {
-            if exclude_patterns.iter().any(|r| r.is_match(path_str)) {
-                continue;
-            }
-
-            result.extend(traverse_path(&file.path(), exclude_patterns, out_dir));
-        } else if file_type.is_file() {
-            let extension = file
-                .path()
-                .extension()
-                .map(|s| s.to_str().unwrap().to_owned())
-                .unwrap_or("".to_string());
+            let path_str = path.to_string_lossy();
 
-            if exclude_patterns.iter().any(|r| r.is_match(path_str)) {
+            if exclude_patterns.iter().any(|r| r.is_match(&path_str)) {
                 continue;
             }
 
-            let possible_extensions = vec!["ts", "tsx", "js", "jsx", "mjs", "mts"];
-            if possible_extensions.contains(&extension.as_str()) {
-                result.push(path);
-            }
+            result.extend(traverse_path(&file_path, exclude_patterns, out_dir)?);
+        } else if file_type.is_file() && is_source_file(&path, exclude_patterns, out_dir) {
+            result.push(path);
         }
     }
 
-    result
+    Ok(result)
 }
 
 #[cfg(test)]
 mod tests {
-    use std::{collections::HashSet, path::PathBuf};
+    use std::{
+        collections::{HashMap, HashSet},
+        fs,
+        path::PathBuf,
+    };
 
     use anyhow::Result;
 
     use crate::{
-        analyze_package::analyze_package,
-        module_symbols::{Import, ImportedSymbol},
+        analyze_package::{analyze_package, analyze_packages, reanalyze_modules, traverse_path},
+        analyze_plan::prepare_analyze_plan,
+        error::Error,
+        module_symbols::{Import, ImportedSymbol, Location},
+        resolve_import_path::ImportKind,
     };
 
     #[test]
@@ -214,10 +644,181 @@ mod tests {
                 from: PathBuf::from("./tests/namespace-imports/src/another.ts")
                     .canonicalize()
                     .unwrap(),
-                symbols: vec![Import::Namespace("A".to_string())]
+                symbols: vec![Import::Namespace("A".to_string())],
+                type_only: false,
+                location: Location { line: 1, column: 1 },
             }])
         );
 
         Ok(())
     }
+
+    #[test]
+    fn import_kinds() -> Result<()> {
+        let analyzed_package = analyze_package(
+            &PathBuf::from("./tests/import-kinds/"),
+            &Default::default(),
+            &Default::default(),
+            Default::default(),
+        )?;
+
+        let to_set = |imports: &[&str]| imports.iter().map(|i| i.to_string()).collect();
+
+        assert_eq!(
+            analyzed_package.imports_by_kind,
+            HashMap::from([
+                (ImportKind::Relative, to_set(&["./local"])),
+                (ImportKind::Builtin, to_set(&["fs", "node:path"])),
+                (ImportKind::External, to_set(&["lodash"])),
+                (ImportKind::Unresolved, to_set(&["not-installed"])),
+            ])
+        );
+        assert_eq!(
+            analyzed_package.unresolved_paths,
+            to_set(&["not-installed"])
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn missing_source_folder() {
+        let missing = PathBuf::from("./tests/namespace-imports/missing");
+        assert!(matches!(
+            traverse_path(&missing, &[], &None),
+            Err(Error::Io { path, .. }) if path == missing
+        ));
+    }
+
+    #[test]
+    fn unbuilt_workspace_package() -> Result<()> {
+        let analyze_plan = prepare_analyze_plan(&PathBuf::from("./tests/unbuilt-package/"))?;
+        let analyzed_packages = analyze_packages(&analyze_plan.packages, &Default::default())?;
+
+        let unresolved_paths = analyzed_packages
+            .iter()
+            .flat_map(|p| p.unresolved_paths.iter().cloned())
+            .collect::<HashSet<_>>();
+        assert_eq!(unresolved_paths, HashSet::from(["lib".to_string()]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn jsx_and_unparsed_files() -> Result<()> {
+        let path = std::env::temp_dir().join(format!(
+            "ts-unused-symbols-unparsed-test-{}",
+            std::process::id()
+        ));
+        fs::create_dir_all(path.join("src"))?;
+        fs::write(
+            path.join("src/app.jsx"),
+            "export const App = () => <div />;",
+        )?;
+        fs::write(
+            path.join("src/view.js"),
+            "export const View = () => <p>view</p>;",
+        )?;
+        fs::write(path.join("src/broken.ts"), "export const = ;")?;
+        let path = path.canonicalize()?;
+
+        let mut analyzed_package = analyze_package(
+            &path,
+            &Default::default(),
+            &Default::default(),
+            Default::default(),
+        )?;
+        assert_eq!(analyzed_package.modules.len(), 2);
+        assert_eq!(
+            analyzed_package.unparsed_files.keys().collect::<Vec<_>>(),
+            vec![&path.join("src/broken.ts")]
+        );
+
+        // a module failing to parse after a change keeps its previous analysis
+        fs::write(path.join("src/app.jsx"), "export const App = () => <div>;")?;
+        fs::write(path.join("src/broken.ts"), "export const fixed = 1;")?;
+        reanalyze_modules(
+            &mut analyzed_package,
+            &HashSet::from([path.join("src/app.jsx"), path.join("src/broken.ts")]),
+            &Default::default(),
+            &Default::default(),
+            Default::default(),
+        )?;
+        assert_eq!(analyzed_package.modules.len(), 3);
+        assert_eq!(
+            analyzed_package.unparsed_files.keys().collect::<Vec<_>>(),
+            vec![&path.join("src/app.jsx")]
+        );
+
+        fs::remove_dir_all(&path)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn reanalyze_modules_after_changes() -> Result<()> {
+        let path = std::env::temp_dir().join(format!(
+            "ts-unused-symbols-reanalyze-test-{}",
+            std::process::id()
+        ));
+        fs::create_dir_all(path.join("src"))?;
+        fs::write(path.join("src/a.ts"), "export const a = 1;")?;
+        fs::write(path.join("src/b.ts"), "export const b = 2;")?;
+        let path = path.canonicalize()?;
+
+        let mut analyzed_package = analyze_package(
+            &path,
+            &Default::default(),
+            &Default::default(),
+            Default::default(),
+        )?;
+        assert_eq!(analyzed_package.modules.len(), 2);
+
+        fs::write(path.join("src/a.ts"), "import { b } from './b';\nb;")?;
+        fs::write(path.join("src/c.ts"), "export const c = 3;")?;
+        fs::remove_file(path.join("src/b.ts"))?;
+
+        let changed = ["a.ts", "b.ts", "c.ts"]
+            .iter()
+            .map(|f| path.join("src").join(f))
+            .collect::<HashSet<_>>();
+        reanalyze_modules(
+            &mut analyzed_package,
+            &changed,
+            &Default::default(),
+            &Default::default(),
+            Default::default(),
+        )?;
+
+        let mut modules = analyzed_package
+            .modules
+            .keys()
+            .map(|p| p.file_name().unwrap().to_str().unwrap().to_owned())
+            .collect::<Vec<_>>();
+        modules.sort();
+        assert_eq!(modules, vec!["a.ts", "c.ts"]);
+        assert_eq!(
+            analyzed_package.unresolved_paths,
+            HashSet::from(["./b".to_string()])
+        );
+
+        // fixed imports aren't unresolved anymore
+        fs::write(path.join("src/a.ts"), "import { c } from './c';\nc;")?;
+        reanalyze_modules(
+            &mut analyzed_package,
+            &HashSet::from([path.join("src/a.ts")]),
+            &Default::default(),
+            &Default::default(),
+            Default::default(),
+        )?;
+        assert_eq!(analyzed_package.unresolved_paths, HashSet::new());
+        assert_eq!(
+            analyzed_package.imports_by_kind,
+            HashMap::from([(ImportKind::Relative, HashSet::from(["./c".to_string()]))])
+        );
+
+        fs::remove_dir_all(&path)?;
+
+        Ok(())
+    }
 }
@@ -1,19 +1,25 @@
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use anyhow::{Context, Result};
-
+use crate::error::{Error, Result};
 use crate::package_json::{try_load_package_json, PackageJson};
+use crate::resolve_import_path::resolve_package_entry_points;
 use crate::tsconfig::{try_load_tsconfig, TsConfig};
 
 #[derive(Debug, Clone)]
 pub struct AnalyzePlan {
     pub packages: Vec<Package>,
+
+    // folders matching the workspaces patterns without a package.json
+    pub skipped_paths: Vec<PathBuf>,
 }
 
 impl AnalyzePlan {
-    pub fn new(packages: Vec<Package>) -> Self {
-        Self { packages }
+    pub fn new(packages: Vec<Package>, skipped_paths: Vec<PathBuf>) -> Self {
+        Self {
+            packages,
+            skipped_paths,
+        }
     }
 }
 
@@ -22,78 +28,157 @@ pub struct Package {
     pub path: PathBuf,
     pub package_json: PackageJson,
     pub tsconfig: Option<TsConfig>,
+    pub entry_points: Vec<PathBuf>,
 }
 
 impl Package {
-    pub fn new(path: &Path, package_json: PackageJson, tsconfig: Option<TsConfig>) -> Self {
+    pub fn new(
+        path: &Path,
+        package_json: PackageJson,
+        tsconfig: Option<TsConfig>,
+        entry_points: Vec<PathBuf>,
+    ) -> Self {
         Self {
             path: path.to_owned(),
             package_json,
             tsconfig,
+            entry_points,
         }
     }
 }
 
 pub fn prepare_analyze_plan(path: &Path) -> Result<AnalyzePlan> {
-    let package_json = try_load_package_json(path)
-        .with_context(|| format!("package.json in {:?} not found", path))?;
+    let package_json =
+        try_load_package_json(path)?.ok_or_else(|| Error::PackageJsonNotFound(path.to_owned()))?;
     let mut packages = vec![];
+    let mut skipped_paths = vec![];
 
     if let Some(monorepo_packages) = package_json.workspaces {
         for monorepo_package_wildcard in monorepo_packages {
-            packages.extend(find_packages(path, &monorepo_package_wildcard));
+            for package_path in get_paths_matching_wildcard(path, &monorepo_package_wildcard)? {
+                match get_package(&package_path)? {
+                    Some(package) => packages.push(package),
+                    None => skipped_paths.push(package_path),
+                }
+            }
         }
     } else {
-        let tsconfig = try_load_tsconfig(path);
-        packages = vec![Package::new(path, package_json, tsconfig)];
+        let tsconfig = try_load_tsconfig(path)?;
+        let entry_points = resolve_package_entry_points(path, &package_json)?;
+        packages = vec![Package::new(path, package_json, tsconfig, entry_points)];
     }
 
-    Ok(AnalyzePlan::new(packages))
+    Ok(AnalyzePlan::new(packages, skipped_paths))
 }
 
-fn find_packages(path: &Path, wildcard: &str) -> Vec<Package> {
-    let package_paths = get_paths_matching_wildcard(path, wildcard);
-    let mut packages = vec![];
+fn get_paths_matching_wildcard(path: &Path, wildcard: &str) -> Result<Vec<PathBuf>> {
+    // currently supports only "<folder-name>/*" and plain folder patterns
+
+    if !wildcard.contains('*') {
+        return Ok(vec![path.join(wildcard)]);
+    }
 
-    for package_path in package_paths {
-        if let Some(package) = get_package(&package_path) {
-            packages.push(package);
-        } else {
-            println!("Package not found in {:?}, skipping...", package_path);
+    let Some(folder) = wildcard
+        .strip_suffix("/*")
+        .filter(|folder| !folder.contains('*'))
+    else {
+        return Err(Error::UnsupportedWorkspacePattern(wildcard.to_owned()));
+    };
+
+    let path = path.join(folder);
+    let io_error = |source| Error::Io {
+        path: path.clone(),
+        source,
+    };
+    let mut paths = vec![];
+
+    for entry in fs::read_dir(&path).map_err(io_error)? {
+        let entry = entry.map_err(io_error)?;
+        if entry.file_type().map_err(io_error)?.is_dir() {
+            paths.push(entry.path());
         }
     }
 
-    packages
+    Ok(paths)
 }
 
-fn get_paths_matching_wildcard(path: &Path, wildcard: &str) -> Vec<PathBuf> {
-    let path_parts = wildcard
-        .split('/')
-        .map(|s| s.to_owned())
-        .collect::<Vec<String>>();
-
-    // currently supports only "<folder-name>/*" pattern
-
-    if path_parts.len() == 2 && path_parts[1] == "*" && !path_parts[0].contains('*') {
-        let mut path = path.to_owned();
-        path.push(PathBuf::from(path_parts[0].clone()));
-
-        return fs::read_dir(path)
-            .unwrap()
-            .map(|f| f.unwrap())
-            .filter(|f| f.file_type().unwrap().is_dir())
-            .map(|f| f.path())
-            .collect();
+fn get_package(path: &Path) -> Result<Option<Package>> {
+    if let Some(package_json) = try_load_package_json(path)? {
+        let tsconfig = try_load_tsconfig(path)?;
+        let entry_points = resolve_package_entry_points(path, &package_json)?;
+        return Ok(Some(Package::new(
+            path,
+            package_json,
+            tsconfig,
+            entry_points,
+        )));
     }
 
-    todo!("{path:?}")
+    Ok(None)
 }
 
-fn get_package(path: &Path) -> Option<Package> {
-    if let Some(package_json) = try_load_package_json(path) {
-        let tsconfig = try_load_tsconfig(path);
-        return Some(Package::new(path, package_json, tsconfig));
+#[cfg(test)]
+mod tests {
+    use std::env::temp_dir;
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    use crate::analyze_plan::{get_paths_matching_wildcard, prepare_analyze_plan};
+    use crate::error::Error;
+
+    #[test]
+    fn workspace_patterns() {
+        let path = Path::new("./tests/dependents");
+
+        let mut paths = get_paths_matching_wildcard(path, "packages/*").unwrap();
+        paths.sort();
+        assert_eq!(
+            paths,
+            vec![path.join("packages/app"), path.join("packages/lib")]
+        );
+        assert_eq!(
+            get_paths_matching_wildcard(path, "packages/app").unwrap(),
+            vec![PathBuf::from("./tests/dependents/packages/app")]
+        );
+
+        assert!(matches!(
+            get_paths_matching_wildcard(path, "packages/**/*"),
+            Err(Error::UnsupportedWorkspacePattern(pattern)) if pattern == "packages/**/*"
+        ));
+        assert!(matches!(
+            get_paths_matching_wildcard(path, "missing/*"),
+            Err(Error::Io { .. })
+        ));
     }
 
-    return None;
+    #[test]
+    fn skipped_paths() {
+        let path = temp_dir().join(format!(
+            "ts-unused-symbols-plan-test-{}",
+            std::process::id()
+        ));
+        fs::create_dir_all(path.join("packages/app")).unwrap();
+        fs::create_dir_all(path.join("packages/notes")).unwrap();
+        fs::write(
+            path.join("package.json"),
+            r#"{ "workspaces": ["packages/*"] }"#,
+        )
+        .unwrap();
+        fs::write(
+            path.join("packages/app/package.json"),
+            r#"{ "name": "app" }"#,
+        )
+        .unwrap();
+
+        let analyze_plan = prepare_analyze_plan(&path);
+        fs::remove_dir_all(&path).unwrap();
+        let analyze_plan = analyze_plan.unwrap();
+
+        assert_eq!(analyze_plan.packages.len(), 1);
+        assert_eq!(analyze_plan.packages[0].path, path.join("packages/app"));
+        assert_eq!(
+            analyze_plan.skipped_paths,
+            vec![path.join("packages/notes")]
+        );
+    }
 }
@@ -1,20 +1,33 @@
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashMap, HashSet};
 
+use swc_common::{SourceMap, Span, SyntaxContext};
 use swc_ecma_ast::{
-    BlockStmt, BlockStmtOrExpr, CallExpr, Callee, Class, ClassMember, Decl, DefaultDecl, Expr,
-    Function, MemberExpr, MemberProp, Module, ModuleDecl, ModuleItem, Prop, PropOrSpread, Stmt,
-    TsInterfaceDecl,
+    Accessibility, AssignTarget, AssignTargetPat, BlockStmt, BlockStmtOrExpr, CallExpr, Callee,
+    Class, ClassMember, Decl, Decorator, DefaultDecl, ExportSpecifier, Expr, ExprOrSpread, ForHead,
+    Function, Ident, JSXAttrOrSpread, JSXAttrValue, JSXElement, JSXElementChild, JSXElementName,
+    JSXExpr, JSXObject, Key, Lit, MemberExpr, MemberProp, Module, ModuleDecl, ModuleExportName,
+    ModuleItem, ObjectPatProp, OptChainBase, ParamOrTsParamProp, Pat, Prop, PropName, PropOrSpread,
+    SimpleAssignTarget, Stmt, SuperProp, TsEntityName, TsEnumMemberId, TsExprWithTypeArgs,
+    TsFnOrConstructorType, TsFnParam, TsInterfaceDecl, TsLit, TsModuleName, TsModuleRef,
+    TsNamespaceBody, TsParamPropParam, TsType, TsTypeAnn, TsTypeElement, TsTypeParamDecl,
+    TsTypeParamInstantiation, TsTypeQueryExpr, TsUnionOrIntersectionType, VarDecl, VarDeclOrExpr,
 };
 
-use crate::module_symbols::{Import, Usage};
+use crate::module_symbols::{Declaration, Import, Location, Member, MemberKind, Usage};
 
 pub struct SymbolsUsageAnalyzer {
     imports: HashSet<Import>,
+
+    // syntax context the resolver assigned to top-level bindings
+    top_level_ctxt: SyntaxContext,
 }
 
 impl SymbolsUsageAnalyzer {
-    pub fn new(imports: HashSet<Import>) -> Self {
-        Self { imports }
+    pub fn new(imports: HashSet<Import>, top_level_ctxt: SyntaxContext) -> Self {
+        Self {
+            imports,
+            top_level_ctxt,
+        }
     }
 
     pub fn analyze_symbols_usage(&self, module: Module) -> HashSet<Usage> {
@@ -26,6 +39,162 @@ impl SymbolsUsageAnalyzer {
             .unwrap_or(Default::default())
     }
 
+    /// Top-level declarations with the symbols referenced from within them and
+    /// the symbols referenced from the rest of the module (side effects, default exports).
+    pub fn analyze_declarations(
+        &self,
+        module: Module,
+        cm: &SourceMap,
+    ) -> (HashSet<Declaration>, HashSet<String>) {
+        let mut declarations = vec![];
+        let mut exported_as: HashMap<String, BTreeSet<String>> = HashMap::new();
+        let mut top_level_references = HashSet::new();
+
+        for item in module.body {
+            match item {
+                ModuleItem::Stmt(Stmt::Decl(decl)) => {
+                    declarations.extend(self.analyze_declaration(decl, false))
+                }
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(decl)) => {
+                    declarations.extend(self.analyze_declaration(decl.decl, true))
+                }
+                ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(decl)) if decl.src.is_none() => {
+                    for specifier in decl.specifiers {
+                        if let ExportSpecifier::Named(s) = specifier {
+                            let local = module_export_name(&s.orig);
+                            let exported = s.exported.as_ref().map(module_export_name);
+                            exported_as
+                                .entry(local.clone())
+                                .or_default()
+                                .insert(exported.unwrap_or(local));
+                        }
+                    }
+                }
+                item => {
+                    // `export default <local>;` exports the local binding as default,
+                    // it isn't a use of the binding within the module
+                    if let ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(decl)) = &item {
+                        if let Expr::Ident(ident) = &*decl.expr {
+                            if ident.ctxt == self.top_level_ctxt {
+                                exported_as
+                                    .entry(ident.sym.to_string())
+                                    .or_default()
+                                    .insert("default".to_string());
+                                continue;
+                            }
+                        }
+                    }
+
+                    top_level_references.extend(symbols(self.analyze_module_item(item)))
+                }
+            }
+        }
+
+        let declarations = declarations
+            .into_iter()
+            .map(|declared| {
+                let name = declared.ident.sym.to_string();
+                let location = |span: Span| {
+                    let loc = cm.lookup_char_pos(span.lo);
+                    Location {
+                        line: loc.line,
+                        column: loc.col.0 + 1,
+                    }
+                };
+
+                let mut exported_names = exported_as.get(&name).cloned().unwrap_or_default();
+                if declared.exported {
+                    exported_names.insert(name.clone());
+                }
+
+                Declaration {
+                    name,
+                    location: location(declared.ident.span),
+                    exported_as: exported_names,
+                    references: symbols(declared.usages).into_iter().collect(),
+                    members: declared
+                        .members
+                        .into_iter()
+                        .map(|(name, span, kind)| Member {
+                            name,
+                            location: location(span),
+                            kind,
+                        })
+                        .collect(),
+                }
+            })
+            .collect();
+
+        (declarations, top_level_references)
+    }
+
+    /// Declared identifiers together with the usages of their declaration.
+    fn analyze_declaration(&self, decl: Decl, exported: bool) -> Vec<DeclaredSymbol> {
+        match decl {
+            Decl::Var(var) => var
+                .decls
+                .into_iter()
+                .flat_map(|decl| {
+                    let members = decl
+                        .init
+                        .as_deref()
+                        .map(const_object_members)
+                        .unwrap_or_default();
+                    let usages = merge_usages(
+                        self.analyze_pat(decl.name.clone()),
+                        self.analyze_option(|e| self.analyze_expr(*e), decl.init),
+                    );
+
+                    pat_idents(decl.name)
+                        .into_iter()
+                        .map(move |ident| DeclaredSymbol {
+                            ident,
+                            exported,
+                            usages: usages.clone(),
+                            members: members.clone(),
+                        })
+                })
+                .collect(),
+            Decl::TsModule(ref m) if matches!(m.id, TsModuleName::Str(_)) => vec![],
+            decl => {
+                let ident = match &decl {
+                    Decl::Class(c) => c.ident.clone(),
+                    Decl::Fn(f) => f.ident.clone(),
+                    Decl::TsEnum(e) => e.id.clone(),
+                    Decl::TsInterface(i) => i.id.clone(),
+                    Decl::TsTypeAlias(t) => t.id.clone(),
+                    Decl::TsModule(m) => match &m.id {
+                        TsModuleName::Ident(i) => i.clone(),
+                        TsModuleName::Str(_) => unreachable!(),
+                    },
+                    Decl::Var(_) | Decl::Using(_) => return vec![],
+                };
+                let members = match &decl {
+                    Decl::TsEnum(e) => e
+                        .members
+                        .iter()
+                        .map(|m| {
+                            let (name, span) = match &m.id {
+                                TsEnumMemberId::Ident(i) => (i.sym.to_string(), i.span),
+                                TsEnumMemberId::Str(s) => (s.value.to_string(), s.span),
+                            };
+                            (name, span, MemberKind::EnumMember)
+                        })
+                        .collect(),
+                    Decl::Class(c) => class_members(&c.class),
+                    _ => vec![],
+                };
+
+                vec![DeclaredSymbol {
+                    ident,
+                    exported,
+                    usages: self.analyze_decl(decl),
+                    members,
+                }]
+            }
+        }
+    }
+
     fn analyze_module_item(&self, module_item: ModuleItem) -> HashSet<Usage> {
         match module_item {
             ModuleItem::Stmt(stmt) => self.analyze_stmt(stmt),
@@ -37,6 +206,25 @@ impl SymbolsUsageAnalyzer {
         match decl {
             ModuleDecl::Import(_) => HashSet::default(),
             ModuleDecl::ExportDecl(decl) => self.analyze_decl(decl.decl),
+            ModuleDecl::ExportNamed(decl) if decl.src.is_none() => decl
+                .specifiers
+                .into_iter()
+                .filter_map(|specifier| match specifier {
+                    ExportSpecifier::Named(s) => {
+                        Some((module_export_name(&s.orig), s.is_type_only))
+                    }
+                    _ => None,
+                })
+                .filter(|(local, _)| self.is_imported_local(local))
+                .map(|(local, is_type_only)| {
+                    // re-exporting an imported binding uses it the way the export does
+                    if decl.type_only || is_type_only {
+                        Usage::Type(local)
+                    } else {
+                        Usage::Symbol(local)
+                    }
+                })
+                .collect(),
             ModuleDecl::ExportNamed(_) => Default::default(),
             ModuleDecl::ExportDefaultDecl(decl) => match decl.decl {
                 DefaultDecl::Fn(decl) => self.analyze_function(*decl.function),
@@ -45,14 +233,30 @@ impl SymbolsUsageAnalyzer {
             },
             ModuleDecl::ExportDefaultExpr(decl) => self.analyze_expr(*decl.expr),
             ModuleDecl::ExportAll(_) => Default::default(),
-            ModuleDecl::TsImportEquals(_) => Default::default(),
-            ModuleDecl::TsExportAssignment(_) => unimplemented!(),
-            ModuleDecl::TsNamespaceExport(_) => unimplemented!(),
+            ModuleDecl::TsImportEquals(decl) => match decl.module_ref {
+                TsModuleRef::TsEntityName(name) => self.analyze_entity_name(name),
+                TsModuleRef::TsExternalModuleRef(_) => HashSet::default(),
+            },
+            ModuleDecl::TsExportAssignment(decl) => self.analyze_expr(*decl.expr),
+            ModuleDecl::TsNamespaceExport(_) => HashSet::default(),
         }
     }
 
     fn analyze_tsinterface(&self, decl: TsInterfaceDecl) -> HashSet<Usage> {
-        todo!("{decl:?}")
+        merge_usages_iter([
+            self.analyze_option(|p| self.analyze_type_params(*p), decl.type_params),
+            merge_usages_iter(
+                decl.extends
+                    .into_iter()
+                    .map(|e| self.analyze_expr_with_type_args(e)),
+            ),
+            merge_usages_iter(
+                decl.body
+                    .body
+                    .into_iter()
+                    .map(|e| self.analyze_type_element(e)),
+            ),
+        ])
     }
 
     fn analyze_stmt(&self, stmt: Stmt) -> HashSet<Usage> {
@@ -96,24 +300,47 @@ impl SymbolsUsageAnalyzer {
             }
             Stmt::For(stmt) => merge_usages(
                 merge_usages(
+                    self.analyze_option(
+                        |init| match init {
+                            VarDeclOrExpr::VarDecl(decl) => self.analyze_var_decl(*decl),
+                            VarDeclOrExpr::Expr(expr) => self.analyze_expr(*expr),
+                        },
+                        stmt.init,
+                    ),
                     self.analyze_option(|s| self.analyze_expr(*s), stmt.test),
+                ),
+                merge_usages(
                     self.analyze_option(|s| self.analyze_expr(*s), stmt.update),
+                    self.analyze_stmt(*stmt.body),
                 ),
-                self.analyze_stmt(*stmt.body),
             ),
-            Stmt::ForIn(stmt) => merge_usages(
+            Stmt::ForIn(stmt) => merge_usages_iter([
+                self.analyze_for_head(stmt.left),
                 self.analyze_expr(*stmt.right),
                 self.analyze_stmt(*stmt.body),
-            ),
-            Stmt::ForOf(stmt) => merge_usages(
+            ]),
+            Stmt::ForOf(stmt) => merge_usages_iter([
+                self.analyze_for_head(stmt.left),
                 self.analyze_expr(*stmt.right),
                 self.analyze_stmt(*stmt.body),
-            ),
+            ]),
             Stmt::Decl(stmt) => self.analyze_decl(stmt),
             Stmt::Expr(stmt) => self.analyze_expr(*stmt.expr),
         }
     }
 
+    fn analyze_for_head(&self, head: ForHead) -> HashSet<Usage> {
+        match head {
+            ForHead::VarDecl(decl) => self.analyze_var_decl(*decl),
+            ForHead::UsingDecl(decl) => merge_usages_iter(
+                decl.decls
+                    .into_iter()
+                    .filter_map(|decl| decl.init.map(|i| self.analyze_expr(*i))),
+            ),
+            ForHead::Pat(pat) => self.analyze_pat(*pat),
+        }
+    }
+
     fn analyze_expr(&self, expr: Expr) -> HashSet<Usage> {
         match expr {
             Expr::This(_) => HashSet::default(),
@@ -127,32 +354,36 @@ impl SymbolsUsageAnalyzer {
                     .into_iter()
                     .map(|e| self.analyze_prop_or_spread(e)),
             ),
-            Expr::Fn(expr) => self.analyze_function(*expr.function), // TODO ident
+            Expr::Fn(expr) => self.analyze_function(*expr.function),
             Expr::Unary(expr) => self.analyze_expr(*expr.arg),
             Expr::Update(expr) => self.analyze_expr(*expr.arg),
             Expr::Bin(expr) => merge_usages(
                 self.analyze_expr(*expr.left),
                 self.analyze_expr(*expr.right),
             ),
-            Expr::Assign(expr) => self.analyze_expr(*expr.right),
+            Expr::Assign(expr) => merge_usages(
+                self.analyze_assign_target(expr.left),
+                self.analyze_expr(*expr.right),
+            ),
             Expr::Member(expr) => self.analyze_member_expr(expr),
-            Expr::SuperProp(_) => HashSet::default(),
+            Expr::SuperProp(expr) => match expr.prop {
+                SuperProp::Ident(prop) => property(&prop.sym),
+                SuperProp::Computed(prop) => self.analyze_expr(*prop.expr),
+            },
             Expr::Cond(expr) => merge_usages(
                 merge_usages(self.analyze_expr(*expr.test), self.analyze_expr(*expr.cons)),
                 self.analyze_expr(*expr.alt),
             ),
             Expr::Call(expr) => self.analyze_call_expr(expr),
-            Expr::New(expr) => merge_usages(
+            Expr::New(expr) => merge_usages_iter([
                 self.analyze_expr(*expr.callee),
-                self.analyze_option(
-                    |es| merge_usages_iter(es.into_iter().map(|e| self.analyze_expr(*e.expr))),
-                    expr.args,
-                ),
-            ),
+                self.analyze_option(|es| self.analyze_args(es), expr.args),
+                self.analyze_option(|t| self.analyze_type_args(*t), expr.type_args),
+            ]),
             Expr::Seq(expr) => {
                 merge_usages_iter(expr.exprs.into_iter().map(|e| self.analyze_expr(*e)))
             }
-            Expr::Ident(_) => HashSet::default(),
+            Expr::Ident(ident) => self.analyze_ident(&ident),
             Expr::Lit(_) => HashSet::default(),
             Expr::Tpl(expr) => {
                 merge_usages_iter(expr.exprs.into_iter().map(|e| self.analyze_expr(*e)))
@@ -161,115 +392,773 @@ impl SymbolsUsageAnalyzer {
                 merge_usages_iter(expr.tpl.exprs.into_iter().map(|e| self.analyze_expr(*e))),
                 self.analyze_expr(*expr.tag),
             ),
-            Expr::Arrow(expr) => match *expr.body {
-                BlockStmtOrExpr::Expr(expr) => self.analyze_expr(*expr),
-                BlockStmtOrExpr::BlockStmt(stmt) => self.analyze_block_stmt(stmt),
-            },
+            Expr::Arrow(expr) => merge_usages_iter([
+                merge_usages_iter(expr.params.into_iter().map(|p| self.analyze_pat(p))),
+                self.analyze_option(|p| self.analyze_type_params(*p), expr.type_params),
+                self.analyze_option(|t| self.analyze_type_ann(*t), expr.return_type),
+                match *expr.body {
+                    BlockStmtOrExpr::Expr(expr) => self.analyze_expr(*expr),
+                    BlockStmtOrExpr::BlockStmt(stmt) => self.analyze_block_stmt(stmt),
+                },
+            ]),
             Expr::Class(c) => self.analyze_class(*c.class),
             Expr::Yield(expr) => self.analyze_option(|e| self.analyze_expr(*e), expr.arg),
             Expr::MetaProp(_) => HashSet::default(),
             Expr::Await(expr) => self.analyze_expr(*expr.arg),
             Expr::Paren(expr) => self.analyze_expr(*expr.expr),
-            Expr::JSXMember(_) => unimplemented!(),
-            Expr::JSXNamespacedName(_) => unimplemented!(),
-            Expr::JSXEmpty(_) => unimplemented!(),
-            Expr::JSXElement(_) => Default::default(), // TODO
-            Expr::JSXFragment(_) => Default::default(), // TODO
-            Expr::TsTypeAssertion(expr) => self.analyze_expr(*expr.expr),
+            Expr::JSXMember(_) => HashSet::default(),
+            Expr::JSXNamespacedName(_) => HashSet::default(),
+            Expr::JSXEmpty(_) => HashSet::default(),
+            Expr::JSXElement(expr) => self.analyze_jsx_element(*expr),
+            Expr::JSXFragment(expr) => self.analyze_jsx_children(expr.children),
+            Expr::TsTypeAssertion(expr) => merge_usages(
+                self.analyze_expr(*expr.expr),
+                self.analyze_type(*expr.type_ann),
+            ),
             Expr::TsConstAssertion(expr) => self.analyze_expr(*expr.expr),
             Expr::TsNonNull(expr) => self.analyze_expr(*expr.expr),
-            Expr::TsAs(expr) => self.analyze_expr(*expr.expr),
-            Expr::TsInstantiation(expr) => self.analyze_expr(*expr.expr),
-            Expr::TsSatisfies(expr) => self.analyze_expr(*expr.expr),
-            Expr::PrivateName(_) => unimplemented!(),
-            Expr::OptChain(_) => Default::default(), // TODO
-            Expr::Invalid(_) => unimplemented!(),
+            Expr::TsAs(expr) => merge_usages(
+                self.analyze_expr(*expr.expr),
+                self.analyze_type(*expr.type_ann),
+            ),
+            Expr::TsInstantiation(expr) => merge_usages(
+                self.analyze_expr(*expr.expr),
+                self.analyze_type_args(*expr.type_args),
+            ),
+            Expr::TsSatisfies(expr) => merge_usages(
+                self.analyze_expr(*expr.expr),
+                self.analyze_type(*expr.type_ann),
+            ),
+            Expr::PrivateName(_) => HashSet::default(),
+            Expr::OptChain(expr) => match *expr.base {
+                OptChainBase::Member(expr) => self.analyze_member_expr(expr),
+                OptChainBase::Call(expr) => merge_usages_iter([
+                    self.analyze_expr(*expr.callee),
+                    self.analyze_args(expr.args),
+                    self.analyze_option(|t| self.analyze_type_args(*t), expr.type_args),
+                ]),
+            },
+            Expr::Invalid(_) => HashSet::default(),
+        }
+    }
+
+    fn analyze_ident(&self, ident: &Ident) -> HashSet<Usage> {
+        if ident.ctxt == self.top_level_ctxt {
+            HashSet::from([Usage::Symbol(ident.sym.to_string())])
+        } else {
+            HashSet::default()
         }
     }
 
     fn analyze_member_expr(&self, expr: MemberExpr) -> HashSet<Usage> {
         match (*expr.obj, expr.prop) {
-            (Expr::Ident(alias), MemberProp::Ident(symbol)) => {
-                let alias = alias.sym.to_string();
-                let symbol = symbol.sym.to_string();
-
-                if self.imports.contains(&Import::Namespace(alias.to_owned())) {
-                    return HashSet::from([Usage::Namespace(symbol, alias)]);
+            (Expr::Ident(obj), MemberProp::Ident(prop)) => {
+                self.analyze_property_access(&obj, &prop.sym, None)
+            }
+            (Expr::Member(inner), MemberProp::Ident(prop)) => match namespace_access(&inner) {
+                Some((obj, symbol)) if self.is_namespace(obj) => {
+                    self.analyze_property_access(obj, symbol, Some(&prop.sym))
                 }
+                _ => merge_usages(self.analyze_member_expr(inner), property(&prop.sym)),
+            },
+            (obj, MemberProp::Ident(prop)) => {
+                merge_usages(self.analyze_expr(obj), property(&prop.sym))
+            }
+            (obj, MemberProp::Computed(prop)) => {
+                let key = match (&obj, &*prop.expr) {
+                    (_, Expr::Lit(Lit::Str(s))) => property(&s.value),
+                    (Expr::This(_), _) => HashSet::from([Usage::DynamicProperty]),
+                    _ => HashSet::default(),
+                };
+
+                merge_usages_iter([self.analyze_expr(obj), self.analyze_expr(*prop.expr), key])
             }
-            (obj, _) => return self.analyze_expr(obj),
+            (obj, MemberProp::PrivateName(_)) => self.analyze_expr(obj),
         }
+    }
 
-        Default::default()
+    fn is_namespace(&self, ident: &Ident) -> bool {
+        ident.ctxt == self.top_level_ctxt && self.is_namespace_alias(&ident.sym)
+    }
+
+    /// `<obj>.<property>` or `<namespace>.<property>.<member>` access.
+    fn analyze_property_access(
+        &self,
+        obj: &Ident,
+        property_name: &str,
+        member: Option<&str>,
+    ) -> HashSet<Usage> {
+        if obj.ctxt != self.top_level_ctxt {
+            return property(property_name);
+        }
+
+        let obj = obj.sym.to_string();
+        let usage = match (self.is_namespace_alias(&obj), member) {
+            (true, Some(member)) => {
+                Usage::NamespaceMember(property_name.to_owned(), member.to_owned(), obj)
+            }
+            (true, None) => Usage::Namespace(property_name.to_owned(), obj),
+            (false, _) => Usage::Member(obj, property_name.to_owned()),
+        };
+
+        HashSet::from([usage])
+    }
+
+    fn is_namespace_alias(&self, alias: &str) -> bool {
+        self.imports.contains(&Import::Namespace(alias.to_owned()))
+    }
+
+    fn is_imported_local(&self, local: &str) -> bool {
+        self.imports.iter().any(|import| match import {
+            Import::Named(_, l) | Import::Default(l) | Import::Namespace(l) => l == local,
+        })
+    }
+
+    fn analyze_assign_target(&self, target: AssignTarget) -> HashSet<Usage> {
+        match target {
+            AssignTarget::Simple(target) => match target {
+                SimpleAssignTarget::Ident(ident) => self.analyze_ident(&ident.id),
+                SimpleAssignTarget::Member(expr) => self.analyze_member_expr(expr),
+                SimpleAssignTarget::Paren(expr) => self.analyze_expr(*expr.expr),
+                SimpleAssignTarget::OptChain(expr) => self.analyze_expr(Expr::OptChain(expr)),
+                SimpleAssignTarget::TsAs(expr) => self.analyze_expr(*expr.expr),
+                SimpleAssignTarget::TsSatisfies(expr) => self.analyze_expr(*expr.expr),
+                SimpleAssignTarget::TsNonNull(expr) => self.analyze_expr(*expr.expr),
+                SimpleAssignTarget::TsTypeAssertion(expr) => self.analyze_expr(*expr.expr),
+                SimpleAssignTarget::TsInstantiation(expr) => self.analyze_expr(*expr.expr),
+                SimpleAssignTarget::SuperProp(_) => HashSet::default(),
+                SimpleAssignTarget::Invalid(_) => HashSet::default(),
+            },
+            AssignTarget::Pat(pat) => match pat {
+                AssignTargetPat::Array(pat) => self.analyze_pat(Pat::Array(pat)),
+                AssignTargetPat::Object(pat) => self.analyze_pat(Pat::Object(pat)),
+                AssignTargetPat::Invalid(_) => HashSet::default(),
+            },
+        }
     }
 
     fn analyze_call_expr(&self, expr: CallExpr) -> HashSet<Usage> {
-        let args = merge_usages_iter(expr.args.into_iter().map(|e| self.analyze_expr(*e.expr)));
+        let args = self.analyze_args(expr.args);
         let callee = match expr.callee {
             Callee::Super(_) => HashSet::default(),
             Callee::Import(_) => HashSet::default(),
             Callee::Expr(expr) => self.analyze_expr(*expr),
         };
-        merge_usages(args, callee)
+        merge_usages_iter([
+            args,
+            callee,
+            self.analyze_option(|t| self.analyze_type_args(*t), expr.type_args),
+        ])
     }
 
-    fn analyze_class(&self, expr: Class) -> HashSet<Usage> {
-        merge_usages_iter(expr.body.into_iter().map(|e| match e {
-            ClassMember::Constructor(expr) => {
-                self.analyze_option(|e| self.analyze_block_stmt(e), expr.body)
+    fn analyze_args(&self, args: Vec<ExprOrSpread>) -> HashSet<Usage> {
+        merge_usages_iter(args.into_iter().map(|e| self.analyze_expr(*e.expr)))
+    }
+
+    fn analyze_jsx_element(&self, element: JSXElement) -> HashSet<Usage> {
+        let name = match element.opening.name {
+            JSXElementName::Ident(ident) => self.analyze_ident(&ident),
+            JSXElementName::JSXMemberExpr(expr) => {
+                let mut obj = expr.obj;
+                loop {
+                    match obj {
+                        JSXObject::JSXMemberExpr(expr) => obj = expr.obj,
+                        JSXObject::Ident(ident) => break self.analyze_ident(&ident),
+                    }
+                }
             }
-            ClassMember::Method(expr) => self.analyze_function(*expr.function),
-            i => todo!("{i:#?}"),
+            JSXElementName::JSXNamespacedName(_) => HashSet::default(),
+        };
+
+        let attrs = merge_usages_iter(element.opening.attrs.into_iter().map(|attr| match attr {
+            JSXAttrOrSpread::JSXAttr(attr) => self.analyze_option(
+                |value| match value {
+                    JSXAttrValue::JSXExprContainer(c) => self.analyze_jsx_expr(c.expr),
+                    JSXAttrValue::JSXElement(e) => self.analyze_jsx_element(*e),
+                    JSXAttrValue::JSXFragment(f) => self.analyze_jsx_children(f.children),
+                    JSXAttrValue::Lit(_) => HashSet::default(),
+                },
+                attr.value,
+            ),
+            JSXAttrOrSpread::SpreadElement(spread) => self.analyze_expr(*spread.expr),
+        }));
+
+        merge_usages_iter([
+            name,
+            attrs,
+            self.analyze_option(|t| self.analyze_type_args(*t), element.opening.type_args),
+            self.analyze_jsx_children(element.children),
+        ])
+    }
+
+    fn analyze_jsx_children(&self, children: Vec<JSXElementChild>) -> HashSet<Usage> {
+        merge_usages_iter(children.into_iter().map(|child| match child {
+            JSXElementChild::JSXExprContainer(c) => self.analyze_jsx_expr(c.expr),
+            JSXElementChild::JSXSpreadChild(c) => self.analyze_expr(*c.expr),
+            JSXElementChild::JSXElement(e) => self.analyze_jsx_element(*e),
+            JSXElementChild::JSXFragment(f) => self.analyze_jsx_children(f.children),
+            JSXElementChild::JSXText(_) => HashSet::default(),
         }))
     }
 
+    fn analyze_jsx_expr(&self, expr: JSXExpr) -> HashSet<Usage> {
+        match expr {
+            JSXExpr::Expr(expr) => self.analyze_expr(*expr),
+            JSXExpr::JSXEmptyExpr(_) => HashSet::default(),
+        }
+    }
+
+    fn analyze_class(&self, expr: Class) -> HashSet<Usage> {
+        let members = merge_usages_iter(expr.body.into_iter().map(|e| match e {
+            ClassMember::Constructor(expr) => merge_usages(
+                merge_usages_iter(expr.params.into_iter().map(|p| match p {
+                    ParamOrTsParamProp::Param(p) => merge_usages(
+                        self.analyze_decorators(p.decorators),
+                        self.analyze_pat(p.pat),
+                    ),
+                    ParamOrTsParamProp::TsParamProp(p) => merge_usages(
+                        self.analyze_decorators(p.decorators),
+                        match p.param {
+                            TsParamPropParam::Ident(i) => {
+                                self.analyze_option(|t| self.analyze_type_ann(*t), i.type_ann)
+                            }
+                            TsParamPropParam::Assign(p) => self.analyze_pat(Pat::Assign(p)),
+                        },
+                    ),
+                })),
+                self.analyze_option(|e| self.analyze_block_stmt(e), expr.body),
+            ),
+            ClassMember::Method(expr) => merge_usages(
+                self.analyze_prop_name(expr.key),
+                self.analyze_function(*expr.function),
+            ),
+            ClassMember::PrivateMethod(expr) => self.analyze_function(*expr.function),
+            ClassMember::ClassProp(expr) => merge_usages_iter([
+                self.analyze_decorators(expr.decorators),
+                self.analyze_prop_name(expr.key),
+                self.analyze_option(|e| self.analyze_expr(*e), expr.value),
+                self.analyze_option(|t| self.analyze_type_ann(*t), expr.type_ann),
+            ]),
+            ClassMember::PrivateProp(expr) => merge_usages_iter([
+                self.analyze_decorators(expr.decorators),
+                self.analyze_option(|e| self.analyze_expr(*e), expr.value),
+                self.analyze_option(|t| self.analyze_type_ann(*t), expr.type_ann),
+            ]),
+            ClassMember::AutoAccessor(expr) => merge_usages_iter([
+                self.analyze_decorators(expr.decorators),
+                match expr.key {
+                    Key::Public(key) => self.analyze_prop_name(key),
+                    Key::Private(_) => HashSet::default(),
+                },
+                self.analyze_option(|e| self.analyze_expr(*e), expr.value),
+                self.analyze_option(|t| self.analyze_type_ann(*t), expr.type_ann),
+            ]),
+            ClassMember::TsIndexSignature(expr) => merge_usages(
+                merge_usages_iter(expr.params.into_iter().map(|p| self.analyze_fn_param(p))),
+                self.analyze_option(|t| self.analyze_type_ann(*t), expr.type_ann),
+            ),
+            ClassMember::StaticBlock(expr) => self.analyze_block_stmt(expr.body),
+            ClassMember::Empty(_) => HashSet::default(),
+        }));
+
+        merge_usages_iter([
+            members,
+            self.analyze_decorators(expr.decorators),
+            self.analyze_option(|e| self.analyze_expr(*e), expr.super_class),
+            self.analyze_option(|p| self.analyze_type_params(*p), expr.type_params),
+            self.analyze_option(|t| self.analyze_type_args(*t), expr.super_type_params),
+            merge_usages_iter(
+                expr.implements
+                    .into_iter()
+                    .map(|e| self.analyze_expr_with_type_args(e)),
+            ),
+        ])
+    }
+
+    fn analyze_decorators(&self, decorators: Vec<Decorator>) -> HashSet<Usage> {
+        merge_usages_iter(decorators.into_iter().map(|d| self.analyze_expr(*d.expr)))
+    }
+
+    fn analyze_prop_name(&self, name: PropName) -> HashSet<Usage> {
+        match name {
+            PropName::Computed(name) => self.analyze_expr(*name.expr),
+            _ => HashSet::default(),
+        }
+    }
+
     fn analyze_prop_or_spread(&self, expr: PropOrSpread) -> HashSet<Usage> {
         match expr {
             PropOrSpread::Prop(p) => match *p {
-                Prop::Shorthand(_) => HashSet::default(),
-                Prop::KeyValue(e) => self.analyze_expr(*e.value),
+                Prop::Shorthand(ident) => self.analyze_ident(&ident),
+                Prop::KeyValue(e) => {
+                    merge_usages(self.analyze_prop_name(e.key), self.analyze_expr(*e.value))
+                }
                 Prop::Assign(e) => self.analyze_expr(*e.value),
-                Prop::Getter(e) => self.analyze_option(|e| self.analyze_block_stmt(e), e.body),
-                Prop::Setter(e) => self.analyze_option(|e| self.analyze_block_stmt(e), e.body),
-                Prop::Method(e) => self.analyze_function(*e.function), // TODO maybe analyze propName
+                Prop::Getter(e) => merge_usages(
+                    self.analyze_prop_name(e.key),
+                    self.analyze_option(|e| self.analyze_block_stmt(e), e.body),
+                ),
+                Prop::Setter(e) => merge_usages_iter([
+                    self.analyze_prop_name(e.key),
+                    self.analyze_pat(*e.param),
+                    self.analyze_option(|e| self.analyze_block_stmt(e), e.body),
+                ]),
+                Prop::Method(e) => merge_usages(
+                    self.analyze_prop_name(e.key),
+                    self.analyze_function(*e.function),
+                ),
             },
             PropOrSpread::Spread(s) => self.analyze_expr(*s.expr),
         }
     }
 
     fn analyze_function(&self, fun: Function) -> HashSet<Usage> {
-        fun.body
-            .map(|b| self.analyze_block_stmt(b))
-            .unwrap_or(Default::default())
+        merge_usages_iter([
+            self.analyze_decorators(fun.decorators),
+            merge_usages_iter(fun.params.into_iter().map(|p| {
+                merge_usages(
+                    self.analyze_decorators(p.decorators),
+                    self.analyze_pat(p.pat),
+                )
+            })),
+            self.analyze_option(|p| self.analyze_type_params(*p), fun.type_params),
+            self.analyze_option(|t| self.analyze_type_ann(*t), fun.return_type),
+            fun.body
+                .map(|b| self.analyze_block_stmt(b))
+                .unwrap_or_default(),
+        ])
+    }
+
+    /// Binding patterns reference symbols only in default values and type annotations.
+    fn analyze_pat(&self, pat: Pat) -> HashSet<Usage> {
+        match pat {
+            Pat::Ident(i) => self.analyze_option(|t| self.analyze_type_ann(*t), i.type_ann),
+            Pat::Array(p) => merge_usages(
+                merge_usages_iter(p.elems.into_iter().flatten().map(|p| self.analyze_pat(p))),
+                self.analyze_option(|t| self.analyze_type_ann(*t), p.type_ann),
+            ),
+            Pat::Rest(p) => merge_usages(
+                self.analyze_pat(*p.arg),
+                self.analyze_option(|t| self.analyze_type_ann(*t), p.type_ann),
+            ),
+            Pat::Object(p) => merge_usages(
+                merge_usages_iter(p.props.into_iter().map(|p| {
+                    match p {
+                        ObjectPatProp::KeyValue(p) => merge_usages_iter([
+                            prop_name(&p.key)
+                                .map(|(name, _)| property(&name))
+                                .unwrap_or_default(),
+                            self.analyze_prop_name(p.key),
+                            self.analyze_pat(*p.value),
+                        ]),
+                        ObjectPatProp::Assign(p) => merge_usages(
+                            property(&p.key.sym),
+                            self.analyze_option(|e| self.analyze_expr(*e), p.value),
+                        ),
+                        ObjectPatProp::Rest(p) => self.analyze_pat(Pat::Rest(p)),
+                    }
+                })),
+                self.analyze_option(|t| self.analyze_type_ann(*t), p.type_ann),
+            ),
+            Pat::Assign(p) => merge_usages(self.analyze_pat(*p.left), self.analyze_expr(*p.right)),
+            Pat::Expr(e) => self.analyze_expr(*e),
+            Pat::Invalid(_) => HashSet::default(),
+        }
+    }
+
+    fn analyze_fn_param(&self, param: TsFnParam) -> HashSet<Usage> {
+        match param {
+            TsFnParam::Ident(i) => self.analyze_pat(Pat::Ident(i)),
+            TsFnParam::Array(p) => self.analyze_pat(Pat::Array(p)),
+            TsFnParam::Rest(p) => self.analyze_pat(Pat::Rest(p)),
+            TsFnParam::Object(p) => self.analyze_pat(Pat::Object(p)),
+        }
+    }
+
+    fn analyze_type_ann(&self, type_ann: TsTypeAnn) -> HashSet<Usage> {
+        self.analyze_type(*type_ann.type_ann)
+    }
+
+    fn analyze_type_args(&self, type_args: TsTypeParamInstantiation) -> HashSet<Usage> {
+        merge_usages_iter(type_args.params.into_iter().map(|t| self.analyze_type(*t)))
+    }
+
+    fn analyze_type_params(&self, type_params: TsTypeParamDecl) -> HashSet<Usage> {
+        merge_usages_iter(type_params.params.into_iter().map(|p| {
+            merge_usages(
+                self.analyze_option(|t| self.analyze_type(*t), p.constraint),
+                self.analyze_option(|t| self.analyze_type(*t), p.default),
+            )
+        }))
+    }
+
+    fn analyze_expr_with_type_args(&self, expr: TsExprWithTypeArgs) -> HashSet<Usage> {
+        merge_usages(
+            self.analyze_expr(*expr.expr),
+            self.analyze_option(|t| self.analyze_type_args(*t), expr.type_args),
+        )
+    }
+
+    /// Only the leftmost identifier of `A.B.C` is a binding of the module.
+    fn analyze_entity_name(&self, name: TsEntityName) -> HashSet<Usage> {
+        match name {
+            TsEntityName::Ident(ident) => self.analyze_ident(&ident),
+            TsEntityName::TsQualifiedName(name) => match &name.left {
+                TsEntityName::Ident(obj) => {
+                    self.analyze_property_access(obj, &name.right.sym, None)
+                }
+                TsEntityName::TsQualifiedName(inner) => match &inner.left {
+                    TsEntityName::Ident(obj) if self.is_namespace(obj) => {
+                        self.analyze_property_access(obj, &inner.right.sym, Some(&name.right.sym))
+                    }
+                    _ => self.analyze_entity_name(name.left),
+                },
+            },
+        }
+    }
+
+    fn analyze_type(&self, ts_type: TsType) -> HashSet<Usage> {
+        match ts_type {
+            TsType::TsKeywordType(_) => HashSet::default(),
+            TsType::TsThisType(_) => HashSet::default(),
+            TsType::TsFnOrConstructorType(t) => {
+                let (params, type_params, type_ann) = match t {
+                    TsFnOrConstructorType::TsFnType(t) => (t.params, t.type_params, t.type_ann),
+                    TsFnOrConstructorType::TsConstructorType(t) => {
+                        (t.params, t.type_params, t.type_ann)
+                    }
+                };
+
+                merge_usages_iter([
+                    merge_usages_iter(params.into_iter().map(|p| self.analyze_fn_param(p))),
+                    self.analyze_option(|p| self.analyze_type_params(*p), type_params),
+                    self.analyze_type_ann(*type_ann),
+                ])
+            }
+            TsType::TsTypeRef(t) => merge_usages(
+                match t.type_name {
+                    TsEntityName::Ident(ident) if ident.ctxt == self.top_level_ctxt => {
+                        HashSet::from([Usage::Type(ident.sym.to_string())])
+                    }
+                    name => self.analyze_entity_name(name),
+                },
+                self.analyze_option(|t| self.analyze_type_args(*t), t.type_params),
+            ),
+            TsType::TsTypeQuery(t) => merge_usages(
+                match t.expr_name {
+                    TsTypeQueryExpr::TsEntityName(name) => self.analyze_entity_name(name),
+                    TsTypeQueryExpr::Import(_) => HashSet::default(),
+                },
+                self.analyze_option(|t| self.analyze_type_args(*t), t.type_args),
+            ),
+            TsType::TsTypeLit(t) => {
+                merge_usages_iter(t.members.into_iter().map(|e| self.analyze_type_element(e)))
+            }
+            TsType::TsArrayType(t) => self.analyze_type(*t.elem_type),
+            TsType::TsTupleType(t) => {
+                merge_usages_iter(t.elem_types.into_iter().map(|e| self.analyze_type(*e.ty)))
+            }
+            TsType::TsOptionalType(t) => self.analyze_type(*t.type_ann),
+            TsType::TsRestType(t) => self.analyze_type(*t.type_ann),
+            TsType::TsUnionOrIntersectionType(t) => {
+                let types = match t {
+                    TsUnionOrIntersectionType::TsUnionType(t) => t.types,
+                    TsUnionOrIntersectionType::TsIntersectionType(t) => t.types,
+                };
+
+                merge_usages_iter(types.into_iter().map(|t| self.analyze_type(*t)))
+            }
+            TsType::TsConditionalType(t) => merge_usages_iter([
+                self.analyze_type(*t.check_type),
+                self.analyze_type(*t.extends_type),
+                self.analyze_type(*t.true_type),
+                self.analyze_type(*t.false_type),
+            ]),
+            TsType::TsInferType(t) => {
+                self.analyze_option(|t| self.analyze_type(*t), t.type_param.constraint)
+            }
+            TsType::TsParenthesizedType(t) => self.analyze_type(*t.type_ann),
+            TsType::TsTypeOperator(t) => self.analyze_type(*t.type_ann),
+            TsType::TsIndexedAccessType(t) => merge_usages(
+                self.analyze_type(*t.obj_type),
+                self.analyze_type(*t.index_type),
+            ),
+            TsType::TsMappedType(t) => merge_usages_iter([
+                self.analyze_option(|t| self.analyze_type(*t), t.type_param.constraint),
+                self.analyze_option(|t| self.analyze_type(*t), t.name_type),
+                self.analyze_option(|t| self.analyze_type(*t), t.type_ann),
+            ]),
+            TsType::TsLitType(t) => match t.lit {
+                TsLit::Tpl(t) => {
+                    merge_usages_iter(t.types.into_iter().map(|t| self.analyze_type(*t)))
+                }
+                _ => HashSet::default(),
+            },
+            TsType::TsTypePredicate(t) => {
+                self.analyze_option(|t| self.analyze_type_ann(*t), t.type_ann)
+            }
+            TsType::TsImportType(t) => {
+                self.analyze_option(|t| self.analyze_type_args(*t), t.type_args)
+            }
+        }
+    }
+
+    fn analyze_type_element(&self, element: TsTypeElement) -> HashSet<Usage> {
+        let computed_key = |computed: bool, key: Box<Expr>| {
+            if computed {
+                self.analyze_expr(*key)
+            } else {
+                HashSet::default()
+            }
+        };
+
+        match element {
+            TsTypeElement::TsCallSignatureDecl(e) => merge_usages_iter([
+                merge_usages_iter(e.params.into_iter().map(|p| self.analyze_fn_param(p))),
+                self.analyze_option(|t| self.analyze_type_ann(*t), e.type_ann),
+                self.analyze_option(|p| self.analyze_type_params(*p), e.type_params),
+            ]),
+            TsTypeElement::TsConstructSignatureDecl(e) => merge_usages_iter([
+                merge_usages_iter(e.params.into_iter().map(|p| self.analyze_fn_param(p))),
+                self.analyze_option(|t| self.analyze_type_ann(*t), e.type_ann),
+                self.analyze_option(|p| self.analyze_type_params(*p), e.type_params),
+            ]),
+            TsTypeElement::TsPropertySignature(e) => merge_usages(
+                computed_key(e.computed, e.key),
+                self.analyze_option(|t| self.analyze_type_ann(*t), e.type_ann),
+            ),
+            TsTypeElement::TsGetterSignature(e) => merge_usages(
+                computed_key(e.computed, e.key),
+                self.analyze_option(|t| self.analyze_type_ann(*t), e.type_ann),
+            ),
+            TsTypeElement::TsSetterSignature(e) => merge_usages(
+                computed_key(e.computed, e.key),
+                self.analyze_fn_param(e.param),
+            ),
+            TsTypeElement::TsMethodSignature(e) => merge_usages_iter([
+                computed_key(e.computed, e.key),
+                merge_usages_iter(e.params.into_iter().map(|p| self.analyze_fn_param(p))),
+                self.analyze_option(|t| self.analyze_type_ann(*t), e.type_ann),
+                self.analyze_option(|p| self.analyze_type_params(*p), e.type_params),
+            ]),
+            TsTypeElement::TsIndexSignature(e) => merge_usages(
+                merge_usages_iter(e.params.into_iter().map(|p| self.analyze_fn_param(p))),
+                self.analyze_option(|t| self.analyze_type_ann(*t), e.type_ann),
+            ),
+        }
     }
 
     fn analyze_option<F, T>(&self, fun: F, value: Option<T>) -> HashSet<Usage>
     where
         F: FnOnce(T) -> HashSet<Usage>,
     {
-        value.map(fun).unwrap_or_else(HashSet::default)
+        value.map(fun).unwrap_or_default()
     }
 
     fn analyze_block_stmt(&self, stmt: BlockStmt) -> HashSet<Usage> {
         merge_usages_iter(stmt.stmts.into_iter().map(|e| self.analyze_stmt(e)))
     }
 
+    fn analyze_var_decl(&self, var: VarDecl) -> HashSet<Usage> {
+        merge_usages_iter(var.decls.into_iter().map(|decl| {
+            merge_usages(
+                self.analyze_pat(decl.name),
+                self.analyze_option(|i| self.analyze_expr(*i), decl.init),
+            )
+        }))
+    }
+
     fn analyze_decl(&self, decl: Decl) -> HashSet<Usage> {
         match decl {
             Decl::Class(class) => self.analyze_class(*class.class),
             Decl::Fn(fun) => self.analyze_function(*fun.function),
-            Decl::Var(var) => merge_usages_iter(
-                var.decls
+            Decl::Var(var) => self.analyze_var_decl(*var),
+            Decl::TsEnum(e) => merge_usages_iter(
+                e.members
+                    .into_iter()
+                    .map(|m| self.analyze_option(|e| self.analyze_expr(*e), m.init)),
+            ),
+            Decl::TsInterface(i) => self.analyze_tsinterface(*i),
+            Decl::TsTypeAlias(t) => merge_usages(
+                self.analyze_option(|p| self.analyze_type_params(*p), t.type_params),
+                self.analyze_type(*t.type_ann),
+            ),
+            Decl::TsModule(m) => self.analyze_option(|b| self.analyze_namespace_body(b), m.body),
+            Decl::Using(u) => merge_usages_iter(
+                u.decls
                     .into_iter()
                     .filter_map(|decl| decl.init.map(|i| self.analyze_expr(*i))),
             ),
-            Decl::TsEnum(_) => HashSet::default(),      // TODO
-            Decl::TsInterface(_) => HashSet::default(), // TODO
-            Decl::TsTypeAlias(_) => HashSet::default(), // TODO
-            Decl::TsModule(_) => HashSet::default(),    // TODO
-            Decl::Using(_) => HashSet::default(),       // TODO
         }
     }
+
+    fn analyze_namespace_body(&self, body: TsNamespaceBody) -> HashSet<Usage> {
+        match body {
+            TsNamespaceBody::TsModuleBlock(block) => merge_usages_iter(
+                block
+                    .body
+                    .into_iter()
+                    .map(|item| self.analyze_module_item(item)),
+            ),
+            TsNamespaceBody::TsNamespaceDecl(decl) => self.analyze_namespace_body(*decl.body),
+        }
+    }
+}
+
+struct DeclaredSymbol {
+    ident: Ident,
+    exported: bool,
+    usages: HashSet<Usage>,
+    members: Vec<(String, Span, MemberKind)>,
+}
+
+/// Properties of an `{ ... } as const` object literal.
+fn const_object_members(init: &Expr) -> Vec<(String, Span, MemberKind)> {
+    let Expr::TsConstAssertion(assertion) = init else {
+        return vec![];
+    };
+    let Expr::Object(object) = &*assertion.expr else {
+        return vec![];
+    };
+
+    object
+        .props
+        .iter()
+        .filter_map(|prop| match prop {
+            PropOrSpread::Prop(prop) => match &**prop {
+                Prop::Shorthand(i) => Some((i.sym.to_string(), i.span)),
+                Prop::KeyValue(p) => prop_name(&p.key),
+                Prop::Method(p) => prop_name(&p.key),
+                Prop::Getter(p) => prop_name(&p.key),
+                _ => None,
+            },
+            PropOrSpread::Spread(_) => None,
+        })
+        .map(|(name, span)| (name, span, MemberKind::ConstObjectProperty))
+        .collect()
+}
+
+fn prop_name(name: &PropName) -> Option<(String, Span)> {
+    match name {
+        PropName::Ident(i) => Some((i.sym.to_string(), i.span)),
+        PropName::Str(s) => Some((s.value.to_string(), s.span)),
+        _ => None,
+    }
+}
+
+/// Public members of a class. Classes extending another class or decorated
+/// ones are skipped, their members are often invoked by a framework.
+fn class_members(class: &Class) -> Vec<(String, Span, MemberKind)> {
+    if class.super_class.is_some() || !class.decorators.is_empty() {
+        return vec![];
+    }
+
+    let is_public = |accessibility: Option<Accessibility>| {
+        accessibility.is_none_or(|a| a == Accessibility::Public)
+    };
+
+    let mut members: Vec<(String, Span)> = vec![];
+
+    for member in &class.body {
+        let member = match member {
+            ClassMember::Method(m)
+                if is_public(m.accessibility) && m.function.decorators.is_empty() =>
+            {
+                prop_name(&m.key)
+            }
+            ClassMember::ClassProp(p)
+                if is_public(p.accessibility) && p.decorators.is_empty() && !p.declare =>
+            {
+                prop_name(&p.key)
+            }
+            ClassMember::AutoAccessor(a)
+                if is_public(a.accessibility) && a.decorators.is_empty() =>
+            {
+                match &a.key {
+                    Key::Public(key) => prop_name(key),
+                    Key::Private(_) => None,
+                }
+            }
+            ClassMember::Constructor(c) => {
+                for param in &c.params {
+                    if let ParamOrTsParamProp::TsParamProp(p) = param {
+                        let is_property = p.accessibility.is_some() || p.readonly;
+                        let ident = match &p.param {
+                            TsParamPropParam::Ident(i) => Some(&i.id),
+                            TsParamPropParam::Assign(a) => a.left.as_ident().map(|i| &i.id),
+                        };
+
+                        if let Some(ident) = ident.filter(|_| {
+                            is_property && is_public(p.accessibility) && p.decorators.is_empty()
+                        }) {
+                            members.push((ident.sym.to_string(), ident.span));
+                        }
+                    }
+                }
+                None
+            }
+            _ => None,
+        };
+
+        members.extend(member);
+    }
+
+    let mut seen = HashSet::new();
+    members
+        .into_iter()
+        .filter(|(name, _)| seen.insert(name.to_owned()))
+        .map(|(name, span)| (name, span, MemberKind::ClassMember))
+        .collect()
+}
+
+/// `<obj>.<symbol>` where `obj` may be a namespace import.
+fn namespace_access(expr: &MemberExpr) -> Option<(&Ident, &str)> {
+    match (&*expr.obj, &expr.prop) {
+        (Expr::Ident(obj), MemberProp::Ident(symbol)) => Some((obj, &symbol.sym)),
+        _ => None,
+    }
+}
+
+fn property(name: &str) -> HashSet<Usage> {
+    HashSet::from([Usage::Property(name.to_owned())])
+}
+
+/// Identifiers bound by a declaration pattern.
+pub fn pat_idents(pat: Pat) -> Vec<Ident> {
+    match pat {
+        Pat::Ident(i) => vec![i.id],
+        Pat::Array(p) => p.elems.into_iter().flatten().flat_map(pat_idents).collect(),
+        Pat::Rest(p) => pat_idents(*p.arg),
+        Pat::Object(p) => p
+            .props
+            .into_iter()
+            .flat_map(|p| match p {
+                ObjectPatProp::KeyValue(p) => pat_idents(*p.value),
+                ObjectPatProp::Assign(p) => vec![p.key.id],
+                ObjectPatProp::Rest(p) => pat_idents(*p.arg),
+            })
+            .collect(),
+        Pat::Assign(p) => pat_idents(*p.left),
+        Pat::Expr(_) | Pat::Invalid(_) => vec![],
+    }
+}
+
+fn symbols(usages: HashSet<Usage>) -> HashSet<String> {
+    usages
+        .into_iter()
+        .filter_map(|usage| match usage {
+            Usage::Symbol(symbol) | Usage::Type(symbol) | Usage::Member(symbol, _) => Some(symbol),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Name of an export or import specifier, identifiers and string literals alike.
+pub fn module_export_name(name: &ModuleExportName) -> String {
+    match name {
+        ModuleExportName::Ident(i) => i.sym.to_string(),
+        ModuleExportName::Str(s) => s.value.to_string(),
+    }
 }
 
 fn merge_usages_iter<Iter: IntoIterator<Item = HashSet<Usage>>>(iter: Iter) -> HashSet<Usage> {
@@ -17,7 +17,7 @@ impl AnalyzedModule<String> {
 impl<P> AnalyzedModule<P> {
     pub fn exports_symbol(&self, symbol: &str) -> bool {
         for s in &self.symbols.exports {
-            if let Export::Symbol(s) = s {
+            if let Export::Symbol(s, _) = s {
                 if s == symbol {
                     return true;
                 }
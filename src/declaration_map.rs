@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use swc_common::sync::Lrc;
+use swc_common::{SourceMap, Span};
+use swc_ecma_ast::{
+    Decl, DefaultDecl, EsVersion, ExportSpecifier, Expr, ModuleDecl, ModuleItem, Pat, Stmt,
+    TsModuleName,
+};
+use swc_ecma_parser::{parse_file_as_module, Syntax, TsSyntax};
+
+use crate::analyze_symbols_usage::module_export_name;
+use crate::error::{Error, Result};
+use crate::source_map::{find_original_position, source_map_path, try_load_source_map};
+
+/// Declaration file bundled from multiple sources. Each exported symbol
+/// is mapped onto the source file it was declared in.
+#[derive(Debug, Clone)]
+pub struct DeclarationMap {
+    pub sources: Vec<PathBuf>,
+    pub exports: HashMap<String, PathBuf>,
+}
+
+impl DeclarationMap {
+    /// Sources declaring the symbol, all the sources if it can't be determined.
+    pub fn sources_of(&self, symbol: &str) -> Vec<PathBuf> {
+        match self.exports.get(symbol) {
+            Some(source) => vec![source.to_owned()],
+            None => self.sources.clone(),
+        }
+    }
+}
+
+pub fn try_load_declaration_map(declaration_path: &Path) -> Result<Option<DeclarationMap>> {
+    let source_map_path = source_map_path(declaration_path);
+
+    let Some(source_map) = try_load_source_map(&source_map_path)? else {
+        return Ok(None);
+    };
+    let Some(source_map_dir) = source_map_path.parent() else {
+        return Ok(None);
+    };
+
+    let resolve_source = |source: &str| source_map_dir.join(source).canonicalize().ok();
+
+    let sources = source_map
+        .resolved_sources()
+        .iter()
+        .filter_map(|s| resolve_source(s))
+        .collect::<Vec<_>>();
+
+    if sources.len() < 2 {
+        return Ok(None);
+    }
+
+    let cm: Lrc<SourceMap> = Default::default();
+    let fm = cm.load_file(declaration_path).map_err(|source| Error::Io {
+        path: declaration_path.to_owned(),
+        source,
+    })?;
+    let syntax = Syntax::Typescript(TsSyntax {
+        dts: true,
+        ..TsSyntax::default()
+    });
+    let Ok(module) = parse_file_as_module(&fm, syntax, EsVersion::EsNext, None, &mut Vec::new())
+    else {
+        return Ok(None);
+    };
+
+    let mut declarations = HashMap::new();
+    let mut exports = vec![];
+
+    for item in module.body {
+        match item {
+            ModuleItem::Stmt(Stmt::Decl(decl)) => declarations.extend(declared_symbols(decl)),
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(decl)) => {
+                for (symbol, span) in declared_symbols(decl.decl) {
+                    exports.push((symbol.clone(), symbol.clone()));
+                    declarations.insert(symbol, span);
+                }
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(decl)) if decl.src.is_none() => {
+                for specifier in decl.specifiers {
+                    if let ExportSpecifier::Named(s) = specifier {
+                        let local = module_export_name(&s.orig);
+                        let exported = s.exported.as_ref().map(module_export_name);
+                        exports.push((exported.unwrap_or(local.clone()), local));
+                    }
+                }
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(decl)) => {
+                let span = match decl.decl {
+                    DefaultDecl::Class(c) => c.class.span,
+                    DefaultDecl::Fn(f) => f.function.span,
+                    DefaultDecl::TsInterfaceDecl(i) => i.id.span,
+                };
+                declarations.insert("default".to_string(), span);
+                exports.push(("default".to_string(), "default".to_string()));
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(decl)) => {
+                if let Expr::Ident(i) = *decl.expr {
+                    exports.push(("default".to_string(), i.sym.to_string()));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mappings = source_map
+        .decode_mappings()
+        .map_err(|message| Error::InvalidSourceMap {
+            path: source_map_path.to_owned(),
+            message,
+        })?;
+
+    let exports = exports
+        .into_iter()
+        .filter_map(|(exported, local)| {
+            let loc = cm.lookup_char_pos(declarations.get(&local)?.lo);
+            let original =
+                find_original_position(&mappings, loc.line as u32 - 1, loc.col.0 as u32)?;
+
+            Some((exported, resolve_source(&original.source)?))
+        })
+        .collect();
+
+    Ok(Some(DeclarationMap { sources, exports }))
+}
+
+fn declared_symbols(decl: Decl) -> Vec<(String, Span)> {
+    match decl {
+        Decl::Class(c) => vec![(c.ident.sym.to_string(), c.ident.span)],
+        Decl::Fn(f) => vec![(f.ident.sym.to_string(), f.ident.span)],
+        Decl::Var(var) => var
+            .decls
+            .into_iter()
+            .filter_map(|d| match d.name {
+                Pat::Ident(i) => Some((i.id.sym.to_string(), i.id.span)),
+                _ => None,
+            })
+            .collect(),
+        Decl::TsEnum(e) => vec![(e.id.sym.to_string(), e.id.span)],
+        Decl::TsInterface(i) => vec![(i.id.sym.to_string(), i.id.span)],
+        Decl::TsTypeAlias(t) => vec![(t.id.sym.to_string(), t.id.span)],
+        Decl::TsModule(m) => match m.id {
+            TsModuleName::Ident(i) => vec![(i.sym.to_string(), i.span)],
+            TsModuleName::Str(_) => vec![],
+        },
+        Decl::Using(_) => vec![],
+    }
+}
@@ -0,0 +1,50 @@
+use std::io;
+use std::path::PathBuf;
+
+/// Errors of the analysis, anything else is reported as findings.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("package.json in {0:?} not found")]
+    PackageJsonNotFound(PathBuf),
+
+    #[error("Failed to resolve {specifier} in {path:?}")]
+    ImportResolution {
+        specifier: String,
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("Couldnt run git")]
+    GitUnavailable(#[source] io::Error),
+
+    #[error("git {command} failed: {message}")]
+    Git { command: String, message: String },
+
+    #[error("Package {0} has neither exports nor types in its package.json")]
+    MissingPackageTypes(String),
+
+    #[error("Unsupported workspaces pattern {0}")]
+    UnsupportedWorkspacePattern(String),
+
+    #[error("Couldnt read {path:?}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("Path {0:?} is not valid UTF-8")]
+    NonUtf8Path(PathBuf),
+
+    #[error("Failed to parse {path:?}: {message}")]
+    Parse { path: PathBuf, message: String },
+
+    #[error("Invalid JSON in {path:?}: {message}")]
+    InvalidJson { path: PathBuf, message: String },
+
+    #[error("Invalid source map {path:?}: {message}")]
+    InvalidSourceMap { path: PathBuf, message: String },
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
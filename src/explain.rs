@@ -0,0 +1,347 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use crate::analyze_package::AnalyzedPackage;
+use crate::analyzed_module::AnalyzedModule;
+use crate::find_unused_exports::{Modules, Symbol};
+use crate::module_symbols::{Export, Import, Location, ReexportKind, Usage};
+
+/// Importers keeping an export alive, the ones re-exporting it are explained
+/// recursively.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Explanation {
+    pub filename: PathBuf,
+    pub symbol: Symbol,
+
+    // exports of package entry points are used from outside
+    pub entry_point: bool,
+    pub importers: Vec<Importer>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Importer {
+    pub filename: PathBuf,
+    pub location: Location,
+    pub kind: ImporterKind,
+
+    // modules forwarding the symbol with `export *`, closest to the importer first
+    pub through: Vec<PathBuf>,
+
+    // the importer exports the imported binding, under the same name or renamed
+    pub reexport: Option<Explanation>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImporterKind {
+    // import { <symbol> } from <from>;
+    Named,
+
+    // import <local> from <from>;
+    Default,
+
+    // import * as <alias> from <from>; <alias>.<symbol>
+    Namespace(String),
+
+    // export { <symbol> as <exported> } from <from>;
+    Reexport,
+}
+
+/// Explain why the export of the module is considered used, `None` when the
+/// module doesn't export the symbol.
+pub fn explain_export(
+    analyzed_packages: &[AnalyzedPackage],
+    path: &Path,
+    symbol: &Symbol,
+) -> Option<Explanation> {
+    let modules = analyzed_packages
+        .iter()
+        .flat_map(|p| p.modules.clone())
+        .collect::<Modules>();
+
+    let module = modules.get(path)?;
+    let exported = match symbol {
+        Symbol::Default => module.symbols.exports.contains(&Export::Default),
+        Symbol::Symbol(s) => module.exports_symbol(s),
+    };
+
+    if !exported {
+        return None;
+    }
+
+    let entry_points = analyzed_packages
+        .iter()
+        .flat_map(|p| p.entry_points.iter().cloned())
+        .collect::<HashSet<_>>();
+
+    Some(explain(
+        path,
+        symbol,
+        &modules,
+        &entry_points,
+        &mut HashSet::new(),
+    ))
+}
+
+fn explain(
+    path: &Path,
+    symbol: &Symbol,
+    modules: &Modules,
+    entry_points: &HashSet<PathBuf>,
+    visited: &mut HashSet<(PathBuf, Symbol)>,
+) -> Explanation {
+    visited.insert((path.to_owned(), symbol.to_owned()));
+
+    let mut importers = vec![];
+
+    for module in modules.values() {
+        for import in &module.symbols.imports {
+            for imported in &import.symbols {
+                let (kind, through, local) = match (imported, symbol) {
+                    (Import::Named(name, local), Symbol::Symbol(s)) if name == s => {
+                        match star_path(&import.from, path, s, modules, &mut HashSet::new()) {
+                            Some(through) => (ImporterKind::Named, through, local),
+                            None => continue,
+                        }
+                    }
+                    (Import::Default(local), Symbol::Default) if import.from == path => {
+                        (ImporterKind::Default, vec![], local)
+                    }
+                    (Import::Namespace(alias), Symbol::Symbol(s))
+                        if import.from == path && uses_namespace_member(module, alias, s) =>
+                    {
+                        importers.push(Importer {
+                            filename: module.path.to_owned(),
+                            location: import.location,
+                            kind: ImporterKind::Namespace(alias.to_owned()),
+                            through: vec![],
+                            reexport: None,
+                        });
+                        continue;
+                    }
+                    _ => continue,
+                };
+
+                let reexported = Symbol::Symbol(local.to_owned());
+                let reexport = (module.exports_symbol(local)
+                    && !visited.contains(&(module.path.to_owned(), reexported.to_owned())))
+                .then(|| explain(&module.path, &reexported, modules, entry_points, visited));
+
+                importers.push(Importer {
+                    filename: module.path.to_owned(),
+                    location: import.location,
+                    kind,
+                    through,
+                    reexport,
+                });
+            }
+        }
+    }
+
+    for module in modules.values() {
+        for reexport in &module.symbols.reexports {
+            let ReexportKind::Named(names) = &reexport.kind else {
+                continue;
+            };
+
+            for (original, exported) in names {
+                let through = match symbol {
+                    Symbol::Default if original == "default" && reexport.from == path => vec![],
+                    Symbol::Symbol(s) if original == s => {
+                        match star_path(&reexport.from, path, s, modules, &mut HashSet::new()) {
+                            Some(through) => through,
+                            None => continue,
+                        }
+                    }
+                    _ => continue,
+                };
+
+                let reexported = Symbol::from_name(exported);
+                let reexport_explanation = (!visited
+                    .contains(&(module.path.to_owned(), reexported.to_owned())))
+                .then(|| explain(&module.path, &reexported, modules, entry_points, visited));
+
+                importers.push(Importer {
+                    filename: module.path.to_owned(),
+                    location: reexport.location,
+                    kind: ImporterKind::Reexport,
+                    through,
+                    reexport: reexport_explanation,
+                });
+            }
+        }
+    }
+
+    importers.sort_by(|a, b| (&a.filename, a.location).cmp(&(&b.filename, b.location)));
+
+    Explanation {
+        filename: path.to_owned(),
+        symbol: symbol.to_owned(),
+        entry_point: entry_points.contains(path),
+        importers,
+    }
+}
+
+/// Modules the symbol imported from `from` passes through via `export *`
+/// before reaching `path`, `None` when it doesn't come from `path`.
+fn star_path(
+    from: &Path,
+    path: &Path,
+    symbol: &str,
+    modules: &Modules,
+    visited: &mut HashSet<PathBuf>,
+) -> Option<Vec<PathBuf>> {
+    if from == path {
+        return Some(vec![]);
+    }
+
+    let module = modules.get(from)?;
+
+    // own exports shadow the ones of `export *`
+    if module.exports_symbol(symbol) || !visited.insert(from.to_owned()) {
+        return None;
+    }
+
+    module
+        .symbols
+        .exports
+        .iter()
+        .find_map(|export| match export {
+            Export::AllFrom(next) => star_path(next, path, symbol, modules, visited).map(|rest| {
+                let mut through = vec![from.to_owned()];
+                through.extend(rest);
+                through
+            }),
+            _ => None,
+        })
+}
+
+fn uses_namespace_member(module: &AnalyzedModule<PathBuf>, alias: &str, symbol: &str) -> bool {
+    module.symbols.usages.iter().any(|usage| match usage {
+        Usage::Namespace(s, a) | Usage::NamespaceMember(s, _, a) => s == symbol && a == alias,
+        _ => false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use crate::{
+        analyze_package::analyze_packages,
+        analyze_plan::prepare_analyze_plan,
+        explain::{explain_export, Explanation, Importer, ImporterKind},
+        find_unused_exports::Symbol,
+        module_symbols::Location,
+    };
+
+    fn importer(
+        filename: &Path,
+        line: usize,
+        kind: ImporterKind,
+        through: Vec<PathBuf>,
+        reexport: Option<Explanation>,
+    ) -> Importer {
+        Importer {
+            filename: filename.to_owned(),
+            location: Location { line, column: 1 },
+            kind,
+            through,
+            reexport,
+        }
+    }
+
+    #[test]
+    fn explain_exports() {
+        let path = PathBuf::from("./tests/explain").canonicalize().unwrap();
+        let analyze_plan = prepare_analyze_plan(&path).unwrap();
+        let analyzed_packages =
+            analyze_packages(&analyze_plan.packages, &Default::default()).unwrap();
+
+        let a = path.join("src/a.ts");
+        let app = path.join("src/app.ts");
+        let barrel = path.join("src/barrel.ts");
+        let index = path.join("src/index.ts");
+
+        assert_eq!(
+            explain_export(&analyzed_packages, &a, &Symbol::Symbol("a".to_string())),
+            Some(Explanation {
+                filename: a.to_owned(),
+                symbol: Symbol::Symbol("a".to_string()),
+                entry_point: false,
+                importers: vec![importer(
+                    &index,
+                    1,
+                    ImporterKind::Named,
+                    vec![barrel.to_owned()],
+                    Some(Explanation {
+                        filename: index.to_owned(),
+                        symbol: Symbol::Symbol("a".to_string()),
+                        entry_point: true,
+                        importers: vec![importer(&app, 3, ImporterKind::Named, vec![], None)],
+                    }),
+                )],
+            })
+        );
+
+        assert_eq!(
+            explain_export(&analyzed_packages, &a, &Symbol::Symbol("b".to_string()))
+                .unwrap()
+                .importers,
+            vec![importer(
+                &app,
+                2,
+                ImporterKind::Namespace("ns".to_string()),
+                vec![],
+                None
+            )]
+        );
+
+        assert_eq!(
+            explain_export(&analyzed_packages, &a, &Symbol::Default)
+                .unwrap()
+                .importers,
+            vec![importer(&app, 1, ImporterKind::Default, vec![], None)]
+        );
+
+        assert_eq!(
+            explain_export(&analyzed_packages, &a, &Symbol::Symbol("d".to_string())),
+            None
+        );
+    }
+
+    #[test]
+    fn explain_named_reexports() {
+        let path = PathBuf::from("./tests/named-reexports")
+            .canonicalize()
+            .unwrap();
+        let analyze_plan = prepare_analyze_plan(&path).unwrap();
+        let analyzed_packages =
+            analyze_packages(&analyze_plan.packages, &Default::default()).unwrap();
+
+        let app = path.join("src/app.ts");
+        let barrel = path.join("src/barrel.ts");
+        let math = path.join("src/math.ts");
+
+        assert_eq!(
+            explain_export(
+                &analyzed_packages,
+                &math,
+                &Symbol::Symbol("sub".to_string())
+            )
+            .unwrap()
+            .importers,
+            vec![importer(
+                &barrel,
+                1,
+                ImporterKind::Reexport,
+                vec![],
+                Some(Explanation {
+                    filename: barrel.to_owned(),
+                    symbol: Symbol::Symbol("minus".to_string()),
+                    entry_point: false,
+                    importers: vec![importer(&app, 1, ImporterKind::Named, vec![], None)],
+                }),
+            )]
+        );
+    }
+}
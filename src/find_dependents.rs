@@ -0,0 +1,154 @@
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+
+use crate::analyze_package::AnalyzedPackage;
+use crate::analyzed_module::AnalyzedModule;
+use crate::find_unused_exports::{resolve_import, Modules, Symbol};
+use crate::module_symbols::{Export, ReexportKind};
+
+/// Module importing or re-exporting the queried module, directly or through
+/// other modules.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dependent {
+    pub filename: PathBuf,
+
+    // length of the shortest import chain, 1 for direct importers
+    pub depth: usize,
+
+    // symbols of the queried module the dependent imports, `export *` included
+    pub symbols: BTreeSet<Symbol>,
+}
+
+/// Direct and transitive dependents of the module across all the packages,
+/// closest first.
+pub fn find_dependents(analyzed_packages: &[AnalyzedPackage], path: &Path) -> Vec<Dependent> {
+    let modules = analyzed_packages
+        .iter()
+        .flat_map(|p| p.modules.clone())
+        .collect::<Modules>();
+
+    let importers = importers_by_module(&modules);
+
+    let mut depths = HashMap::from([(path.to_owned(), 0)]);
+    let mut queue = VecDeque::from([path.to_owned()]);
+
+    while let Some(current) = queue.pop_front() {
+        let depth = depths[&current];
+
+        for importer in importers.get(current.as_path()).into_iter().flatten() {
+            if !depths.contains_key(*importer) {
+                depths.insert(importer.to_path_buf(), depth + 1);
+                queue.push_back(importer.to_path_buf());
+            }
+        }
+    }
+
+    let mut dependents = depths
+        .into_iter()
+        .filter(|(_, depth)| *depth > 0)
+        .map(|(filename, depth)| {
+            let module = &modules[&filename];
+            let symbols = module
+                .symbols
+                .imports
+                .iter()
+                .flat_map(|import| {
+                    import.symbols.iter().flat_map(|symbol| {
+                        resolve_import(symbol, &import.from, &module.symbols, &modules)
+                    })
+                })
+                .filter(|(_, from)| from == path)
+                .map(|(symbol, _)| symbol)
+                .chain(reexported_symbols(module, path))
+                .collect();
+
+            Dependent {
+                filename,
+                depth,
+                symbols,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    dependents.sort_by(|a, b| (a.depth, &a.filename).cmp(&(b.depth, &b.filename)));
+    dependents
+}
+
+/// Symbols of the module the `export { ... } from` statements of `reexporting`
+/// forward.
+fn reexported_symbols<'a>(
+    reexporting: &'a AnalyzedModule<PathBuf>,
+    path: &'a Path,
+) -> impl Iterator<Item = Symbol> + 'a {
+    reexporting
+        .symbols
+        .reexports
+        .iter()
+        .filter(move |reexport| reexport.from == path)
+        .flat_map(|reexport| match &reexport.kind {
+            ReexportKind::Named(symbols) => symbols.as_slice(),
+            ReexportKind::All => &[],
+        })
+        .filter(|(original, _)| original != "*")
+        .map(|(original, _)| Symbol::from_name(original))
+}
+
+/// Modules importing or re-exporting each module.
+fn importers_by_module(modules: &Modules) -> HashMap<&Path, HashSet<&Path>> {
+    let mut result: HashMap<&Path, HashSet<&Path>> = HashMap::new();
+
+    for module in modules.values() {
+        let imports = module.symbols.imports.iter().map(|i| &i.from);
+        let reexports = module.symbols.reexports.iter().map(|r| &r.from);
+        let all_exports = module.symbols.exports.iter().filter_map(|e| match e {
+            Export::AllFrom(from) => Some(from),
+            _ => None,
+        });
+
+        for from in imports.chain(reexports).chain(all_exports) {
+            result.entry(from).or_default().insert(&module.path);
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+    use std::path::PathBuf;
+
+    use crate::{
+        analyze_package::analyze_packages,
+        analyze_plan::prepare_analyze_plan,
+        find_dependents::{find_dependents, Dependent},
+        find_unused_exports::Symbol,
+    };
+
+    #[test]
+    fn dependents() {
+        let path = PathBuf::from("./tests/dependents").canonicalize().unwrap();
+        let analyze_plan = prepare_analyze_plan(&path).unwrap();
+        let analyzed_packages =
+            analyze_packages(&analyze_plan.packages, &Default::default()).unwrap();
+
+        let dependent = |filename: &str, depth, symbols: &[&str]| Dependent {
+            filename: path.join("packages").join(filename),
+            depth,
+            symbols: symbols
+                .iter()
+                .map(|s| Symbol::Symbol(s.to_string()))
+                .collect::<BTreeSet<_>>(),
+        };
+
+        assert_eq!(
+            find_dependents(&analyzed_packages, &path.join("packages/lib/src/format.ts")),
+            vec![
+                dependent("lib/src/index.ts", 1, &[]),
+                dependent("lib/src/local.ts", 1, &["parse"]),
+                dependent("app/src/app.ts", 2, &["format"]),
+                dependent("app/src/main.ts", 3, &[]),
+            ]
+        );
+    }
+}
@@ -0,0 +1,280 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::path::PathBuf;
+
+use crate::analyze_package::AnalyzedPackage;
+use crate::module_symbols::Export;
+
+type Graph<N> = BTreeMap<N, BTreeSet<N>>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportCycle {
+    // all the nodes of the strongly connected component, sorted
+    pub nodes: Vec<PathBuf>,
+
+    // shortest cycle within the component, the first node is repeated at the end
+    pub shortest_path: Vec<PathBuf>,
+}
+
+/// Find import cycles between modules. Type-only imports are erased at
+/// runtime and can be left out of the graph.
+pub fn find_module_cycles(
+    analyzed_packages: &[AnalyzedPackage],
+    include_type_imports: bool,
+) -> Vec<ImportCycle> {
+    find_cycles(&module_graph(analyzed_packages, include_type_imports))
+}
+
+/// Find import cycles between packages of the workspace.
+pub fn find_package_cycles(
+    analyzed_packages: &[AnalyzedPackage],
+    include_type_imports: bool,
+) -> Vec<ImportCycle> {
+    let package_by_module = analyzed_packages
+        .iter()
+        .flat_map(|p| p.modules.keys().map(|m| (m, &p.path)))
+        .collect::<HashMap<_, _>>();
+
+    let mut graph = Graph::new();
+
+    for (from, to) in module_graph(analyzed_packages, include_type_imports) {
+        let from_package = package_by_module[&from];
+        let edges = graph
+            .entry(from_package.to_owned())
+            .or_insert_with(BTreeSet::new);
+
+        for to in to {
+            match package_by_module.get(&to) {
+                Some(&to_package) if to_package != from_package => {
+                    edges.insert(to_package.to_owned());
+                }
+                _ => {}
+            }
+        }
+    }
+
+    find_cycles(&graph)
+}
+
+/// Edges from importing modules to the imported ones, re-exports included.
+fn module_graph(
+    analyzed_packages: &[AnalyzedPackage],
+    include_type_imports: bool,
+) -> Graph<PathBuf> {
+    analyzed_packages
+        .iter()
+        .flat_map(|p| p.modules.values())
+        .map(|module| {
+            let imports = module
+                .symbols
+                .imports
+                .iter()
+                .filter(|i| include_type_imports || !i.type_only)
+                .map(|i| i.from.to_owned());
+
+            let reexports = module.symbols.reexports.iter().map(|r| r.from.to_owned());
+
+            let all_exports = module.symbols.exports.iter().filter_map(|e| match e {
+                Export::AllFrom(from) => Some(from.to_owned()),
+                _ => None,
+            });
+
+            (
+                module.path.to_owned(),
+                imports.chain(reexports).chain(all_exports).collect(),
+            )
+        })
+        .collect()
+}
+
+fn find_cycles(graph: &Graph<PathBuf>) -> Vec<ImportCycle> {
+    strongly_connected_components(graph)
+        .into_iter()
+        .filter_map(|component| {
+            let shortest_path = shortest_cycle(graph, &component)?;
+
+            Some(ImportCycle {
+                nodes: component.into_iter().collect(),
+                shortest_path,
+            })
+        })
+        .collect()
+}
+
+/// Tarjan's algorithm, iterative so deep import chains don't overflow the stack.
+/// Components come out sorted by their smallest node.
+fn strongly_connected_components<N: Ord + Clone>(graph: &Graph<N>) -> Vec<BTreeSet<N>> {
+    let empty = BTreeSet::new();
+    let successors = |node: &N| graph.get(node).unwrap_or(&empty).iter().collect::<Vec<_>>();
+
+    let mut index: BTreeMap<&N, usize> = BTreeMap::new();
+    let mut low_link: BTreeMap<&N, usize> = BTreeMap::new();
+    let mut stack: Vec<&N> = vec![];
+    let mut on_stack: BTreeSet<&N> = BTreeSet::new();
+    let mut components = vec![];
+
+    for root in graph.keys() {
+        if index.contains_key(root) {
+            continue;
+        }
+
+        // (node, successors left to visit)
+        let mut call_stack = vec![(root, successors(root).into_iter())];
+        index.insert(root, index.len());
+        low_link.insert(root, index[root]);
+        stack.push(root);
+        on_stack.insert(root);
+
+        while let Some((node, children)) = call_stack.last_mut() {
+            let node = *node;
+
+            if let Some(child) = children.next() {
+                if !index.contains_key(child) {
+                    index.insert(child, index.len());
+                    low_link.insert(child, index[child]);
+                    stack.push(child);
+                    on_stack.insert(child);
+                    call_stack.push((child, successors(child).into_iter()));
+                } else if on_stack.contains(child) {
+                    low_link.insert(node, low_link[node].min(index[child]));
+                }
+                continue;
+            }
+
+            call_stack.pop();
+
+            if let Some((parent, _)) = call_stack.last() {
+                low_link.insert(parent, low_link[parent].min(low_link[node]));
+            }
+
+            if low_link[node] == index[node] {
+                let mut component = BTreeSet::new();
+
+                while let Some(member) = stack.pop() {
+                    on_stack.remove(member);
+                    component.insert(member.to_owned());
+
+                    if member == node {
+                        break;
+                    }
+                }
+
+                components.push(component);
+            }
+        }
+    }
+
+    components.sort();
+    components
+}
+
+/// Shortest cycle through the nodes of a component, `None` for a single node
+/// not importing itself.
+fn shortest_cycle<N: Ord + Clone>(graph: &Graph<N>, component: &BTreeSet<N>) -> Option<Vec<N>> {
+    let mut shortest: Option<Vec<N>> = None;
+
+    for start in component {
+        // breadth-first search back to the start within the component
+        let mut previous: BTreeMap<&N, &N> = BTreeMap::new();
+        let mut queue = VecDeque::from([start]);
+
+        'search: while let Some(node) = queue.pop_front() {
+            for next in graph.get(node).into_iter().flatten() {
+                if !component.contains(next) {
+                    continue;
+                }
+
+                if next == start {
+                    let mut path = vec![start.to_owned()];
+                    let mut current = node;
+
+                    while current != start {
+                        path.push(current.to_owned());
+                        current = previous[current];
+                    }
+
+                    path.push(start.to_owned());
+                    path.reverse();
+
+                    if shortest.as_ref().is_none_or(|s| path.len() < s.len()) {
+                        shortest = Some(path);
+                    }
+                    break 'search;
+                }
+
+                if !previous.contains_key(next) {
+                    previous.insert(next, node);
+                    queue.push_back(next);
+                }
+            }
+        }
+    }
+
+    shortest
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::{
+        find_import_cycles::{find_module_cycles, find_package_cycles},
+        test_utils::analyze_monorepo,
+    };
+
+    fn file_names(paths: &[PathBuf]) -> Vec<&str> {
+        paths
+            .iter()
+            .map(|p| p.file_name().unwrap().to_str().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn import_cycles() {
+        let analyzed_packages = analyze_monorepo("./tests/import-cycles/");
+
+        let cycles = find_module_cycles(&analyzed_packages, true);
+
+        assert_eq!(cycles.len(), 2);
+        // modules only importing from a cycle aren't part of it
+        assert!(cycles
+            .iter()
+            .all(|c| !file_names(&c.nodes).contains(&"index.ts")));
+        assert_eq!(
+            file_names(&cycles[0].nodes),
+            vec!["a.ts", "b.ts", "c.ts", "d.ts"]
+        );
+        assert_eq!(
+            file_names(&cycles[0].shortest_path),
+            vec!["a.ts", "d.ts", "a.ts"]
+        );
+        assert_eq!(
+            file_names(&cycles[1].shortest_path),
+            vec!["model.ts", "types.ts", "model.ts"]
+        );
+
+        let cycles = find_module_cycles(&analyzed_packages, false);
+
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(
+            file_names(&cycles[0].shortest_path),
+            vec!["a.ts", "d.ts", "a.ts"]
+        );
+
+        assert!(find_package_cycles(&analyzed_packages, true).is_empty());
+    }
+
+    #[test]
+    fn reexport_cycles() {
+        let analyzed_packages = analyze_monorepo("./tests/reexport-cycles/");
+
+        let cycles = find_module_cycles(&analyzed_packages, false);
+
+        // closed by `export { b } from "./b"`, the barrel only re-exports from it
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(file_names(&cycles[0].nodes), vec!["a.ts", "b.ts"]);
+        assert_eq!(
+            file_names(&cycles[0].shortest_path),
+            vec!["a.ts", "b.ts", "a.ts"]
+        );
+    }
+}
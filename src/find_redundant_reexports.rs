@@ -0,0 +1,160 @@
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use crate::analyze_package::AnalyzedPackage;
+use crate::analyzed_module::AnalyzedModule;
+use crate::find_unused_exports::{get_all_imports, star_exports, Modules, Symbol};
+use crate::module_symbols::{Export, Location, ReexportKind};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RedundantReexport {
+    pub filename: PathBuf,
+    pub location: Location,
+    pub from: PathBuf,
+}
+
+/// Find `export * from` and `export { ... } from` statements forwarding no
+/// symbol imported by anyone. Statements of dead barrels are left out, the
+/// whole file is reported by [`find_dead_barrels`].
+pub fn find_redundant_reexports(analyzed_packages: &[AnalyzedPackage]) -> Vec<RedundantReexport> {
+    let modules = analyzed_packages
+        .iter()
+        .flat_map(|p| p.modules.clone())
+        .collect::<Modules>();
+
+    let all_imports = get_all_imports(&modules);
+    let dead_barrels = find_dead_barrels(analyzed_packages)
+        .into_iter()
+        .collect::<HashSet<_>>();
+
+    let mut redundant_reexports = modules
+        .values()
+        .filter(|module| !dead_barrels.contains(&module.path))
+        .flat_map(|module| {
+            let is_imported = |symbol: &str| {
+                all_imports.contains(&(Symbol::Symbol(symbol.to_owned()), module.path.to_owned()))
+            };
+
+            module
+                .symbols
+                .reexports
+                .iter()
+                .filter(|reexport| match &reexport.kind {
+                    ReexportKind::Named(symbols) => !symbols.iter().any(|(_, s)| is_imported(s)),
+                    // targets outside of the analyzed sources can't be checked
+                    ReexportKind::All => {
+                        modules.contains_key(&reexport.from)
+                            && !star_exports(&reexport.from, &modules, &mut HashSet::new())
+                                .into_keys()
+                                .any(|s| !module.exports_symbol(&s) && is_imported(&s))
+                    }
+                })
+                .map(|reexport| RedundantReexport {
+                    filename: module.path.to_owned(),
+                    location: reexport.location,
+                    from: reexport.from.to_owned(),
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    redundant_reexports.sort_by(|a, b| (&a.filename, a.location).cmp(&(&b.filename, b.location)));
+    redundant_reexports
+}
+
+/// Find modules consisting of re-exports only which no module imports or
+/// re-exports.
+pub fn find_dead_barrels(analyzed_packages: &[AnalyzedPackage]) -> Vec<PathBuf> {
+    let modules = analyzed_packages
+        .iter()
+        .flat_map(|p| p.modules.values())
+        .collect::<Vec<_>>();
+
+    let consumed = modules
+        .iter()
+        .flat_map(|module| {
+            let imports = module.symbols.imports.iter().map(|i| &i.from);
+            let reexports = module.symbols.reexports.iter().map(|r| &r.from);
+            let all_exports = module.symbols.exports.iter().filter_map(|e| match e {
+                Export::AllFrom(from) => Some(from),
+                _ => None,
+            });
+
+            imports.chain(reexports).chain(all_exports)
+        })
+        .collect::<HashSet<_>>();
+
+    let mut dead_barrels = modules
+        .iter()
+        .filter(|module| is_barrel(module) && !consumed.contains(&module.path))
+        .map(|module| module.path.to_owned())
+        .collect::<Vec<_>>();
+
+    dead_barrels.sort();
+    dead_barrels
+}
+
+/// Whether all the module does is re-exporting other modules.
+fn is_barrel(module: &AnalyzedModule<PathBuf>) -> bool {
+    let symbols = &module.symbols;
+
+    let reexported = symbols
+        .reexports
+        .iter()
+        .flat_map(|r| match &r.kind {
+            ReexportKind::Named(symbols) => symbols.as_slice(),
+            ReexportKind::All => &[],
+        })
+        .map(|(_, exported)| exported)
+        .collect::<HashSet<_>>();
+
+    !symbols.reexports.is_empty()
+        && symbols.imports.is_empty()
+        && symbols.declarations.is_empty()
+        && symbols.top_level_references.is_empty()
+        && symbols.exports.iter().all(|e| match e {
+            Export::Symbol(s, _) => reexported.contains(s),
+            Export::AllFrom(_) => true,
+            Export::Default => false,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::{
+        find_redundant_reexports::{find_dead_barrels, find_redundant_reexports},
+        test_utils::analyze_monorepo,
+    };
+
+    #[test]
+    fn redundant_reexports() {
+        let path = PathBuf::from("./tests/redundant-reexports/");
+        let analyzed_packages = analyze_monorepo("./tests/redundant-reexports/");
+
+        let redundant_reexports = find_redundant_reexports(&analyzed_packages)
+            .into_iter()
+            .map(|r| {
+                (
+                    r.location.line,
+                    r.from.file_name().unwrap().to_str().unwrap().to_owned(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        // the renamed reexport of `sub` is redundant, `add` is imported through the barrel
+        assert_eq!(
+            redundant_reexports,
+            vec![(2, "strings.ts".to_string()), (3, "math.ts".to_string())]
+        );
+        assert!(!redundant_reexports.contains(&(1, "math.ts".to_string())));
+
+        assert_eq!(
+            find_dead_barrels(&analyzed_packages),
+            vec![path.join("src/legacy/index.ts").canonicalize().unwrap()]
+        );
+        assert!(!find_dead_barrels(&analyzed_packages)
+            .contains(&path.join("src/utils/index.ts").canonicalize().unwrap()));
+    }
+}
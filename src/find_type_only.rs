@@ -0,0 +1,159 @@
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use crate::analyze_package::AnalyzedPackage;
+use crate::find_unused_exports::{resolve_import, Modules, Symbol};
+use crate::module_symbols::{Export, Import, Location, SymbolKind, Usage};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeOnlyExport {
+    pub filename: PathBuf,
+    pub symbol: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TypeOnlyImport {
+    pub filename: PathBuf,
+    pub location: Location,
+    pub symbol: String,
+}
+
+/// Find value exports (classes, functions, variables, ...) which are imported
+/// but never used as values by any of the importing modules.
+pub fn find_type_only_exports(analyzed_packages: &[AnalyzedPackage]) -> Vec<TypeOnlyExport> {
+    let modules = analyzed_packages
+        .iter()
+        .flat_map(|p| p.modules.clone())
+        .collect::<Modules>();
+
+    let mut imported = HashSet::new();
+    let mut used_as_value = HashSet::new();
+
+    for module in modules.values() {
+        for import in &module.symbols.imports {
+            for symbol in &import.symbols {
+                let resolved = resolve_import(symbol, &import.from, &module.symbols, &modules);
+
+                let is_value_use = !import.type_only
+                    && match symbol {
+                        Import::Named(_, local) | Import::Default(local) => {
+                            is_used_as_value(&module.symbols.usages, local)
+                        }
+                        // members of a namespace can't be told apart
+                        Import::Namespace(_) => true,
+                    };
+
+                if is_value_use {
+                    used_as_value.extend(resolved.iter().cloned());
+                }
+
+                imported.extend(resolved);
+            }
+        }
+    }
+
+    let mut type_only_exports = modules
+        .values()
+        .flat_map(|module| {
+            module
+                .symbols
+                .exports
+                .iter()
+                .filter_map(|export| match export {
+                    Export::Symbol(s, SymbolKind::Value) => Some(s),
+                    _ => None,
+                })
+                .map(|s| (Symbol::Symbol(s.to_owned()), module.path.to_owned()))
+        })
+        .filter(|export| imported.contains(export) && !used_as_value.contains(export))
+        .filter_map(|(symbol, filename)| match symbol {
+            Symbol::Symbol(symbol) => Some(TypeOnlyExport { filename, symbol }),
+            Symbol::Default => None,
+        })
+        .collect::<Vec<_>>();
+
+    type_only_exports.sort_by(|a, b| (&a.filename, &a.symbol).cmp(&(&b.filename, &b.symbol)));
+    type_only_exports
+}
+
+/// Find bindings imported without `type` which are only referenced in type
+/// positions, so the import would be elided under `isolatedModules`.
+pub fn find_type_only_imports(analyzed_packages: &[AnalyzedPackage]) -> Vec<TypeOnlyImport> {
+    let mut type_only_imports = analyzed_packages
+        .iter()
+        .flat_map(|p| p.modules.values())
+        .flat_map(|module| {
+            let usages = &module.symbols.usages;
+
+            module
+                .symbols
+                .imports
+                .iter()
+                .filter(|import| !import.type_only)
+                .flat_map(move |import| {
+                    import
+                        .symbols
+                        .iter()
+                        .filter_map(|symbol| match symbol {
+                            Import::Named(_, local) | Import::Default(local) => Some(local),
+                            Import::Namespace(_) => None,
+                        })
+                        .filter(|local| {
+                            usages.contains(&Usage::Type(local.to_string()))
+                                && !is_used_as_value(usages, local)
+                        })
+                        .map(move |local| TypeOnlyImport {
+                            filename: module.path.to_owned(),
+                            location: import.location,
+                            symbol: local.to_owned(),
+                        })
+                })
+        })
+        .collect::<Vec<_>>();
+
+    // imports split by declaration maps share the location
+    type_only_imports.sort();
+    type_only_imports.dedup();
+    type_only_imports
+}
+
+fn is_used_as_value(usages: &HashSet<Usage>, local: &str) -> bool {
+    usages.iter().any(|usage| match usage {
+        Usage::Symbol(symbol) | Usage::Member(symbol, _) => symbol == local,
+        _ => false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        find_type_only::{find_type_only_exports, find_type_only_imports},
+        test_utils::analyze_monorepo,
+    };
+
+    #[test]
+    fn type_only() {
+        let analyzed_packages = analyze_monorepo("./tests/type-only/");
+
+        let type_only_exports = find_type_only_exports(&analyzed_packages)
+            .into_iter()
+            .map(|e| e.symbol)
+            .collect::<Vec<_>>();
+
+        // values imported under another name or only exported as types aren't reported
+        assert_eq!(type_only_exports, vec!["Order", "User"]);
+
+        let type_only_imports = find_type_only_imports(&analyzed_packages)
+            .into_iter()
+            .map(|i| (i.symbol, i.location.line))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            type_only_imports,
+            vec![("Order".to_string(), 2), ("Settings".to_string(), 2)]
+        );
+        assert!(type_only_imports
+            .iter()
+            .all(|(symbol, _)| symbol != "createOrder" && symbol != "Product"));
+    }
+}
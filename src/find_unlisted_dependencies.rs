@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use crate::analyze_package::AnalyzedPackage;
+use crate::error::Result;
+use crate::module_symbols::Location;
+use crate::package_json::{try_load_package_json, PackageJson};
+use crate::resolve_import_path::{package_name, types_package_name, ImportKind};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnlistedDependency {
+    pub package_path: PathBuf,
+    pub name: String,
+    pub kind: ImportKind,
+    pub filename: PathBuf,
+    pub location: Location,
+}
+
+/// Find bare imports of external packages or workspace siblings which are not
+/// declared in the package.json of the importing package.
+pub fn find_unlisted_dependencies(
+    analyzed_packages: &[AnalyzedPackage],
+) -> Result<Vec<UnlistedDependency>> {
+    let mut unlisted_dependencies = vec![];
+
+    for analyzed_package in analyzed_packages {
+        if let Some(package_json) = try_load_package_json(&analyzed_package.path)? {
+            unlisted_dependencies.extend(find_unlisted_package_dependencies(
+                analyzed_package,
+                &package_json,
+            ));
+        }
+    }
+
+    Ok(unlisted_dependencies)
+}
+
+fn find_unlisted_package_dependencies(
+    analyzed_package: &AnalyzedPackage,
+    package_json: &PackageJson,
+) -> Vec<UnlistedDependency> {
+    let kinds = analyzed_package
+        .imports_by_kind
+        .iter()
+        .flat_map(|(kind, imports)| imports.iter().map(move |i| (i.as_str(), *kind)))
+        .collect::<HashMap<&str, ImportKind>>();
+
+    let mut unlisted_dependencies = analyzed_package
+        .modules
+        .values()
+        .flat_map(|module| {
+            module
+                .symbols
+                .references
+                .iter()
+                .map(move |reference| (module, reference))
+        })
+        .filter_map(|(module, reference)| {
+            let kind = *kinds.get(reference.specifier.as_str())?;
+
+            if !matches!(kind, ImportKind::External | ImportKind::Workspace) {
+                return None;
+            }
+
+            let name = package_name(&reference.specifier);
+
+            // listing `@types/foo` declares `foo` as well
+            if name == package_json.name
+                || package_json.declares_dependency(name)
+                || package_json.declares_dependency(&types_package_name(name))
+            {
+                return None;
+            }
+
+            Some(UnlistedDependency {
+                package_path: analyzed_package.path.to_owned(),
+                name: name.to_owned(),
+                kind,
+                filename: module.path.to_owned(),
+                location: reference.location,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    unlisted_dependencies.sort_by(|a, b| (&a.filename, a.location).cmp(&(&b.filename, b.location)));
+    unlisted_dependencies
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        find_unlisted_dependencies::find_unlisted_dependencies, module_symbols::Location,
+        resolve_import_path::ImportKind, test_utils::analyze_monorepo,
+    };
+
+    #[test]
+    fn unlisted_dependencies() {
+        let analyzed_packages = analyze_monorepo("./tests/unlisted-dependencies/");
+
+        let unlisted_dependencies = find_unlisted_dependencies(&analyzed_packages)
+            .unwrap()
+            .into_iter()
+            .map(|d| (d.name, d.kind, d.location))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            unlisted_dependencies,
+            vec![
+                (
+                    "lodash".to_string(),
+                    ImportKind::External,
+                    Location { line: 2, column: 1 }
+                ),
+                (
+                    "lib".to_string(),
+                    ImportKind::Workspace,
+                    Location { line: 4, column: 1 }
+                ),
+                (
+                    "dayjs".to_string(),
+                    ImportKind::External,
+                    Location {
+                        line: 7,
+                        column: 21
+                    }
+                ),
+                (
+                    "uuid".to_string(),
+                    ImportKind::External,
+                    Location {
+                        line: 8,
+                        column: 16
+                    }
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,301 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+
+use crate::analyze_package::AnalyzedPackage;
+use crate::error::Result;
+use crate::package_json::{try_load_package_json, PackageJson};
+use crate::resolve_import_path::{package_name, ImportKind};
+
+/// Config files scanned for references to dependencies (eslint plugins, babel presets, ...).
+const DEFAULT_CONFIG_FILES: [&str; 16] = [
+    ".babelrc",
+    ".babelrc.json",
+    "babel.config.js",
+    "babel.config.json",
+    ".eslintrc",
+    ".eslintrc.js",
+    ".eslintrc.json",
+    "eslint.config.js",
+    "eslint.config.mjs",
+    ".prettierrc",
+    "prettier.config.js",
+    "jest.config.js",
+    "jest.config.ts",
+    "vite.config.ts",
+    "webpack.config.js",
+    "tsconfig.json",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DependencyKind {
+    Dependency,
+    DevDependency,
+    PeerDependency,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnusedDependency {
+    pub package_path: PathBuf,
+    pub name: String,
+    pub kind: DependencyKind,
+}
+
+#[derive(Debug, Default)]
+pub struct DependencyOptions {
+    // packages providing an executable are never reported
+    pub allow_binaries: bool,
+
+    // `@types/*` packages are never reported
+    pub allow_types_packages: bool,
+
+    // additional config files referencing dependencies
+    pub config_files: Vec<String>,
+
+    pub ignore_patterns: Vec<Regex>,
+}
+
+impl DependencyOptions {
+    pub fn new(
+        allow_binaries: bool,
+        allow_types_packages: bool,
+        config_files: Vec<String>,
+        ignore_patterns: Vec<Regex>,
+    ) -> Self {
+        Self {
+            allow_binaries,
+            allow_types_packages,
+            config_files,
+            ignore_patterns,
+        }
+    }
+}
+
+pub fn find_unused_dependencies(
+    analyzed_packages: &[AnalyzedPackage],
+    options: &DependencyOptions,
+) -> Result<Vec<UnusedDependency>> {
+    let mut unused_dependencies = vec![];
+
+    for analyzed_package in analyzed_packages {
+        if let Some(package_json) = try_load_package_json(&analyzed_package.path)? {
+            unused_dependencies.extend(find_unused_package_dependencies(
+                analyzed_package,
+                &package_json,
+                options,
+            )?);
+        }
+    }
+
+    Ok(unused_dependencies)
+}
+
+fn find_unused_package_dependencies(
+    analyzed_package: &AnalyzedPackage,
+    package_json: &PackageJson,
+    options: &DependencyOptions,
+) -> Result<Vec<UnusedDependency>> {
+    let imported_packages = imported_packages(analyzed_package);
+    let imports_builtin = analyzed_package
+        .imports_by_kind
+        .contains_key(&ImportKind::Builtin);
+    let config_files_content = read_config_files(&analyzed_package.path, &options.config_files);
+    let scripts = package_json
+        .scripts
+        .values()
+        .flat_map(|s| s.split_whitespace())
+        .collect::<HashSet<&str>>();
+
+    let is_used = |name: &str| {
+        if imported_packages.contains(name)
+            || options.ignore_patterns.iter().any(|r| r.is_match(name))
+        {
+            return Ok(true);
+        }
+
+        if let Some(types_for) = name.strip_prefix("@types/") {
+            if options.allow_types_packages || (types_for == "node" && imports_builtin) {
+                return Ok(true);
+            }
+
+            // @types/scope__name provides types for @scope/name
+            let types_for = match types_for.split_once("__") {
+                Some((scope, name)) => format!("@{scope}/{name}"),
+                None => types_for.to_owned(),
+            };
+
+            return Ok(imported_packages.contains(&types_for));
+        }
+
+        if config_files_content
+            .iter()
+            .any(|c| references_package(c, name))
+        {
+            return Ok(true);
+        }
+
+        let binaries = load_dependency_package_json(&analyzed_package.path, name)?
+            .map(|p| p.binaries())
+            .unwrap_or_default();
+
+        if !binaries.is_empty() && options.allow_binaries {
+            return Ok(true);
+        }
+
+        Ok(binaries.iter().any(|b| scripts.contains(b.as_str())))
+    };
+
+    let dependencies = [
+        (&package_json.dependencies, DependencyKind::Dependency),
+        (
+            &package_json.dev_dependencies,
+            DependencyKind::DevDependency,
+        ),
+        (
+            &package_json.peer_dependencies,
+            DependencyKind::PeerDependency,
+        ),
+    ];
+
+    let mut unused_dependencies = vec![];
+
+    for (dependencies, kind) in dependencies {
+        for name in dependencies.keys() {
+            if !is_used(name)? {
+                unused_dependencies.push(UnusedDependency {
+                    package_path: analyzed_package.path.to_owned(),
+                    name: name.to_owned(),
+                    kind,
+                });
+            }
+        }
+    }
+
+    Ok(unused_dependencies)
+}
+
+/// Package names of all the bare import specifiers.
+fn imported_packages(analyzed_package: &AnalyzedPackage) -> HashSet<String> {
+    analyzed_package
+        .imports_by_kind
+        .iter()
+        .filter(|(kind, _)| {
+            matches!(
+                kind,
+                ImportKind::External | ImportKind::Workspace | ImportKind::Unresolved
+            )
+        })
+        .flat_map(|(_, imports)| imports)
+        .filter(|i| !i.starts_with('.') && !i.starts_with('#'))
+        .map(|i| package_name(i).to_owned())
+        .collect()
+}
+
+fn read_config_files(package_path: &Path, config_files: &[String]) -> Vec<String> {
+    DEFAULT_CONFIG_FILES
+        .iter()
+        .map(|f| f.to_string())
+        .chain(config_files.iter().cloned())
+        .filter_map(|f| fs::read_to_string(package_path.join(f)).ok())
+        .collect()
+}
+
+/// Whether the content has the package name, or a path within the package,
+/// as a string literal.
+fn references_package(content: &str, name: &str) -> bool {
+    ['"', '\'', '`'].iter().any(|quote| {
+        content.contains(&format!("{quote}{name}{quote}"))
+            || content.contains(&format!("{quote}{name}/"))
+    })
+}
+
+/// Dependencies can be hoisted to any of the parent node_modules.
+fn load_dependency_package_json(package_path: &Path, name: &str) -> Result<Option<PackageJson>> {
+    for path in package_path.ancestors() {
+        if let Some(package_json) = try_load_package_json(&path.join("node_modules").join(name))? {
+            return Ok(Some(package_json));
+        }
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::env::temp_dir;
+    use std::fs;
+    use std::path::PathBuf;
+
+    use crate::{
+        analyze_package::analyze_package,
+        error::Error,
+        find_unused_dependencies::{
+            find_unused_dependencies, load_dependency_package_json, references_package,
+            DependencyOptions,
+        },
+    };
+
+    #[test]
+    fn config_file_references() {
+        let content = r#"{ "plugins": ["react", 'jest/globals'], "parser": `ts-parser` }"#;
+
+        assert!(references_package(content, "react"));
+        assert!(references_package(content, "jest"));
+        assert!(references_package(content, "ts-parser"));
+        assert!(!references_package(content, "re"));
+        assert!(!references_package(content, "ts"));
+        assert!(!references_package(content, "globals"));
+    }
+
+    #[test]
+    fn unused_dependencies() {
+        let analyzed_packages = [analyze_package(
+            &PathBuf::from("./tests/unused-dependencies/"),
+            &Default::default(),
+            &Default::default(),
+            Default::default(),
+        )
+        .unwrap()];
+
+        let unused_dependencies = |options: &DependencyOptions| {
+            find_unused_dependencies(&analyzed_packages, options)
+                .unwrap()
+                .into_iter()
+                .map(|d| d.name)
+                .collect::<HashSet<String>>()
+        };
+
+        assert_eq!(
+            unused_dependencies(&Default::default()),
+            HashSet::from([
+                "left-pad".to_string(),
+                "@types/jest".to_string(),
+                "prettier".to_string()
+            ])
+        );
+        assert_eq!(
+            unused_dependencies(&DependencyOptions::new(true, true, vec![], vec![])),
+            HashSet::from(["left-pad".to_string()])
+        );
+    }
+
+    #[test]
+    fn invalid_dependency_package_json() {
+        let path = temp_dir().join(format!(
+            "ts-unused-symbols-deps-test-{}",
+            std::process::id()
+        ));
+        fs::create_dir_all(path.join("node_modules/broken")).unwrap();
+        fs::write(path.join("node_modules/broken/package.json"), "{ \"bin\": ").unwrap();
+
+        let result = load_dependency_package_json(&path, "broken");
+        let missing = load_dependency_package_json(&path, "missing");
+        fs::remove_dir_all(&path).unwrap();
+
+        assert!(matches!(result, Err(Error::InvalidJson { .. })));
+        assert!(missing.unwrap().is_none());
+    }
+}
@@ -1,11 +1,11 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     path::{Path, PathBuf},
 };
 
 use crate::{
     analyze_package::AnalyzedPackage,
-    module_symbols::{Export, Import},
+    module_symbols::{Export, Import, ReexportKind, Usage},
 };
 use crate::{analyzed_module::AnalyzedModule, module_symbols::ModuleSymbols};
 
@@ -13,24 +13,61 @@ use crate::{analyzed_module::AnalyzedModule, module_symbols::ModuleSymbols};
 pub struct UnusedExport {
     pub filename: PathBuf,
     pub symbol: Symbol,
+    pub kind: UnusedExportKind,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum UnusedExportKind {
+    // never referenced anywhere
+    Unused,
+
+    // referenced in the declaring module, only the `export` can be dropped
+    UsedLocally,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum Symbol {
     Default,
     Symbol(String),
 }
 
+impl Symbol {
+    /// Symbol of an export specifier name, `default` being the default export.
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "default" => Symbol::Default,
+            s => Symbol::Symbol(s.to_owned()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateExport {
+    pub filename: PathBuf,
+    pub symbol: String,
+    pub kind: DuplicateExportKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DuplicateExportKind {
+    // `export *` of the same name from different modules, TypeScript drops the name
+    AmbiguousReexport(Vec<PathBuf>),
+
+    // the binding is exported under its name as well as the default export
+    NamedAndDefault,
+}
+
 impl UnusedExport {
-    fn new(path: &Path, symbol: Symbol) -> Self {
+    fn new(path: &Path, symbol: Symbol, kind: UnusedExportKind) -> Self {
         Self {
             filename: path.to_owned(),
             symbol,
+            kind,
         }
     }
 }
 
-type Modules = HashMap<PathBuf, AnalyzedModule<PathBuf>>;
+pub type Modules = HashMap<PathBuf, AnalyzedModule<PathBuf>>;
 
 pub fn find_unused_exports(analyzed_packages: &[AnalyzedPackage]) -> Vec<UnusedExport> {
     let modules = analyzed_packages
@@ -42,16 +79,141 @@ pub fn find_unused_exports(analyzed_packages: &[AnalyzedPackage]) -> Vec<UnusedE
     let all_exports = get_all_exports(&modules);
 
     let not_imported_exports = all_exports.difference(&all_imports);
-    not_imported_exports
+    let mut unused_exports = not_imported_exports
         .into_iter()
-        .map(|(symbol, path)| UnusedExport::new(path, symbol.to_owned()))
-        .collect()
+        .map(|(symbol, path)| {
+            let kind = match symbol {
+                Symbol::Symbol(s) if is_used_locally(&modules[path], s) => {
+                    UnusedExportKind::UsedLocally
+                }
+                _ => UnusedExportKind::Unused,
+            };
+
+            UnusedExport::new(path, symbol.to_owned(), kind)
+        })
+        .collect::<Vec<_>>();
+
+    unused_exports.sort_by(|a, b| (&a.filename, &a.symbol).cmp(&(&b.filename, &b.symbol)));
+    unused_exports
 }
 
-fn get_all_imports(modules: &Modules) -> HashSet<(Symbol, PathBuf)> {
-    modules
+/// Find names which the `export * from` statements of a module re-export from
+/// more than one module, and bindings exported both by name and as default.
+pub fn find_duplicate_exports(analyzed_packages: &[AnalyzedPackage]) -> Vec<DuplicateExport> {
+    let modules = analyzed_packages
         .iter()
-        .flat_map(|(_, module)| {
+        .flat_map(|p| p.modules.clone())
+        .collect::<Modules>();
+
+    let mut duplicate_exports = vec![];
+
+    for module in modules.values() {
+        // name -> (modules re-exported by `export *` providing it, declaring modules)
+        let mut reexported: BTreeMap<String, (BTreeSet<&Path>, BTreeSet<PathBuf>)> =
+            BTreeMap::new();
+
+        for export in &module.symbols.exports {
+            if let Export::AllFrom(from) = export {
+                let mut visited = HashSet::from([module.path.as_path()]);
+
+                for (symbol, origins) in star_exports(from, &modules, &mut visited) {
+                    let entry = reexported.entry(symbol).or_default();
+                    entry.0.insert(from);
+                    entry.1.extend(origins);
+                }
+            }
+        }
+
+        for (symbol, (sources, origins)) in reexported {
+            // names contributed by a single `export *` are the problem of that module
+            if sources.len() > 1 && origins.len() > 1 && !module.exports_symbol(&symbol) {
+                duplicate_exports.push(DuplicateExport {
+                    filename: module.path.to_owned(),
+                    symbol,
+                    kind: DuplicateExportKind::AmbiguousReexport(origins.into_iter().collect()),
+                });
+            }
+        }
+
+        for declaration in &module.symbols.declarations {
+            if declaration.exported_as.contains("default") && declaration.exported_as.len() > 1 {
+                duplicate_exports.push(DuplicateExport {
+                    filename: module.path.to_owned(),
+                    symbol: declaration.name.to_owned(),
+                    kind: DuplicateExportKind::NamedAndDefault,
+                });
+            }
+        }
+    }
+
+    duplicate_exports.sort_by(|a, b| (&a.filename, &a.symbol).cmp(&(&b.filename, &b.symbol)));
+    duplicate_exports
+}
+
+/// Names a module exports (`export *` doesn't forward the default export)
+/// with the modules declaring them.
+pub fn star_exports<'a>(
+    path: &'a Path,
+    modules: &'a Modules,
+    visited: &mut HashSet<&'a Path>,
+) -> HashMap<String, BTreeSet<PathBuf>> {
+    let mut result: HashMap<String, BTreeSet<PathBuf>> = HashMap::new();
+
+    let Some(module) = modules.get(path) else {
+        return result;
+    };
+
+    if !visited.insert(path) {
+        return result;
+    }
+
+    for export in &module.symbols.exports {
+        if let Export::AllFrom(from) = export {
+            for (symbol, origins) in star_exports(from, modules, visited) {
+                if !module.exports_symbol(&symbol) {
+                    result.entry(symbol).or_default().extend(origins);
+                }
+            }
+        }
+    }
+
+    for export in &module.symbols.exports {
+        if let Export::Symbol(symbol, _) = export {
+            result.insert(symbol.to_owned(), BTreeSet::from([path.to_owned()]));
+        }
+    }
+
+    result
+}
+
+/// Whether the local binding of the exported symbol is referenced within the
+/// module by anything other than its own declaration.
+fn is_used_locally(module: &AnalyzedModule<PathBuf>, symbol: &str) -> bool {
+    let locals = module
+        .symbols
+        .declarations
+        .iter()
+        .filter(|d| d.exported_as.contains(symbol))
+        .map(|d| d.name.as_str())
+        .collect::<HashSet<&str>>();
+
+    module
+        .symbols
+        .top_level_references
+        .iter()
+        .any(|r| locals.contains(r.as_str()))
+        || module
+            .symbols
+            .declarations
+            .iter()
+            .filter(|d| !locals.contains(d.name.as_str()))
+            .any(|d| d.references.iter().any(|r| locals.contains(r.as_str())))
+}
+
+pub fn get_all_imports(modules: &Modules) -> HashSet<(Symbol, PathBuf)> {
+    modules
+        .values()
+        .flat_map(|module| {
             module
                 .symbols
                 .imports
@@ -70,30 +232,33 @@ fn get_all_imports(modules: &Modules) -> HashSet<(Symbol, PathBuf)> {
         .collect()
 }
 
-fn resolve_import(
+pub fn resolve_import(
     import: &Import,
     from: &Path,
     module_symbols: &ModuleSymbols<PathBuf>,
     modules: &Modules,
 ) -> HashSet<(Symbol, PathBuf)> {
     let imports = match import {
-        Import::Named(s) => {
-            let mut resolved = try_follow_reexports(s, from, modules);
-            resolved.insert((Symbol::Symbol(s.to_owned()), from.to_owned()));
+        Import::Named(s, _) => resolve_named_import(s, from, modules),
+        Import::Default(_) => {
+            let mut resolved =
+                try_follow_reexports(&Symbol::Default, from, modules, &mut HashSet::new());
+            resolved.insert((Symbol::Default, from.to_owned()));
             resolved
         }
-        Import::Default(_) => HashSet::from([(Symbol::Default, from.to_owned())]),
         Import::Namespace(alias) => module_symbols
             .usages
             .iter()
             .filter_map(|usage| match usage {
-                crate::module_symbols::Usage::Namespace(symbol, current_alias) => {
+                Usage::Namespace(symbol, current_alias)
+                | Usage::NamespaceMember(symbol, _, current_alias) => {
                     if current_alias == alias {
                         Some(symbol)
                     } else {
                         None
                     }
                 }
+                _ => None,
             })
             .map(|symbol| (Symbol::Symbol(symbol.to_owned()), from.to_owned()))
             .collect(),
@@ -102,10 +267,22 @@ fn resolve_import(
     imports
 }
 
+/// Module exporting the symbol together with all the modules it's re-exported from.
+pub fn resolve_named_import(
+    symbol: &str,
+    from: &Path,
+    modules: &Modules,
+) -> HashSet<(Symbol, PathBuf)> {
+    let symbol = Symbol::Symbol(symbol.to_owned());
+    let mut resolved = try_follow_reexports(&symbol, from, modules, &mut HashSet::new());
+    resolved.insert((symbol, from.to_owned()));
+    resolved
+}
+
 fn get_all_exports(modules: &Modules) -> HashSet<(Symbol, PathBuf)> {
     modules
-        .iter()
-        .flat_map(|(path, module)| {
+        .values()
+        .flat_map(|module| {
             module
                 .symbols
                 .exports
@@ -113,42 +290,101 @@ fn get_all_exports(modules: &Modules) -> HashSet<(Symbol, PathBuf)> {
                 .filter_map(|export| match export {
                     Export::Default => None,
                     Export::AllFrom(_) => None,
-                    Export::Symbol(s) => Some((Symbol::Symbol(s.to_owned()), path.to_owned())),
+                    Export::Symbol(s, _) => {
+                        Some((Symbol::Symbol(s.to_owned()), module.path.to_owned()))
+                    }
                 })
                 .collect::<HashSet<(Symbol, PathBuf)>>()
         })
         .collect()
 }
 
+/// Modules exporting the symbol which the `export *` and `export { ... } from`
+/// statements of `from` forward, renames included. Own exports shadow the ones
+/// of `export *`, targets outside of the analysis (excluded or build output)
+/// are skipped.
 fn try_follow_reexports(
-    symbol: &str,
+    symbol: &Symbol,
     from: &Path,
     modules: &Modules,
+    visited: &mut HashSet<PathBuf>,
 ) -> HashSet<(Symbol, PathBuf)> {
     let mut resolved = HashSet::new();
-    let imported_module = modules.get(from);
-
-    if let Some(imported_module) = imported_module {
-        for export in &imported_module.symbols.exports {
-            match export {
-                Export::Symbol(exported_symbol) => {
-                    if symbol == exported_symbol {
-                        break;
-                    }
-                }
-                Export::AllFrom(new_from) => {
-                    let module = modules
-                        .get(new_from)
-                        .unwrap_or_else(|| panic!("Couldnt find {new_from:?}"));
 
-                    if module.exports_symbol(symbol.as_ref()) {
-                        resolved.insert((Symbol::Symbol(symbol.to_owned()), new_from.to_owned()));
-                    }
-                    resolved.extend(try_follow_reexports(symbol, new_from, modules));
-                }
-                Export::Default => {}
+    let Some(imported_module) = modules.get(from) else {
+        return resolved;
+    };
+
+    if !visited.insert(from.to_owned()) {
+        return resolved;
+    }
+
+    let name = match symbol {
+        Symbol::Default => "default",
+        Symbol::Symbol(s) => s.as_str(),
+    };
+
+    // export { <original> as <name> } from <new_from>;
+    let renamed = imported_module
+        .symbols
+        .reexports
+        .iter()
+        .filter_map(|reexport| match &reexport.kind {
+            ReexportKind::Named(symbols) => Some(
+                symbols
+                    .iter()
+                    .filter(|(_, exported)| exported == name)
+                    .map(|(original, _)| (original, &reexport.from)),
+            ),
+            ReexportKind::All => None,
+        })
+        .flatten()
+        .collect::<BTreeSet<_>>();
+
+    if !renamed.is_empty() {
+        for (original, new_from) in renamed {
+            // export * as <name> from <new_from>; uses the whole module
+            if original == "*" {
+                continue;
             }
+
+            let original = Symbol::from_name(original);
+
+            resolved.extend(try_follow_reexports(&original, new_from, modules, visited));
+            resolved.insert((original, new_from.to_owned()));
+        }
+
+        return resolved;
+    }
+
+    // `export *` doesn't forward the default export
+    let Symbol::Symbol(name) = symbol else {
+        return resolved;
+    };
+
+    if imported_module.exports_symbol(name) {
+        return resolved;
+    }
+
+    let all_from = imported_module
+        .symbols
+        .exports
+        .iter()
+        .filter_map(|export| match export {
+            Export::AllFrom(new_from) => Some(new_from),
+            _ => None,
+        })
+        .collect::<BTreeSet<_>>();
+
+    for new_from in all_from {
+        let Some(module) = modules.get(new_from) else {
+            continue;
+        };
+
+        if module.exports_symbol(name) {
+            resolved.insert((symbol.to_owned(), new_from.to_owned()));
         }
+        resolved.extend(try_follow_reexports(symbol, new_from, modules, visited));
     }
 
     resolved
@@ -156,9 +392,17 @@ fn try_follow_reexports(
 
 #[cfg(test)]
 mod tests {
-    use std::path::PathBuf;
+    use std::{collections::HashSet, path::PathBuf};
 
-    use crate::{analyze_package::analyze_package, find_unused_exports::find_unused_exports};
+    use crate::{
+        analyze_package::analyze_package,
+        find_unused_exports::{
+            find_duplicate_exports, find_unused_exports, DuplicateExportKind, Symbol,
+            UnusedExportKind,
+        },
+        test_utils::analyze_monorepo,
+        tsconfig::try_load_tsconfig,
+    };
 
     #[test]
     fn relative_imports() {
@@ -186,6 +430,66 @@ mod tests {
         assert_eq!(unused_exports.len(), 0);
     }
 
+    #[test]
+    fn named_reexports() {
+        let analyzed_package = analyze_package(
+            &PathBuf::from("./tests/named-reexports/"),
+            &Default::default(),
+            &Default::default(),
+            Default::default(),
+        )
+        .unwrap();
+        let unused_exports = find_unused_exports(&[analyzed_package])
+            .into_iter()
+            .map(|e| {
+                (
+                    e.filename.file_name().unwrap().to_str().unwrap().to_owned(),
+                    e.symbol,
+                )
+            })
+            .collect::<HashSet<_>>();
+
+        // `sub` is imported as `minus` through the barrel
+        assert_eq!(
+            unused_exports,
+            HashSet::from([
+                (
+                    "barrel.ts".to_string(),
+                    Symbol::Symbol("format".to_string())
+                ),
+                ("barrel.ts".to_string(), Symbol::Symbol("mul".to_string())),
+                ("math.ts".to_string(), Symbol::Symbol("mul".to_string())),
+            ])
+        );
+    }
+
+    #[test]
+    fn star_reexports() {
+        let analyzed_package = analyze_package(
+            &PathBuf::from("./tests/star-reexports/"),
+            &Default::default(),
+            &Default::default(),
+            Default::default(),
+        )
+        .unwrap();
+        let unused_exports = find_unused_exports(&[analyzed_package])
+            .into_iter()
+            .map(|e| {
+                (
+                    e.filename.file_name().unwrap().to_str().unwrap().to_owned(),
+                    e.symbol,
+                )
+            })
+            .collect::<HashSet<_>>();
+        assert_eq!(
+            unused_exports,
+            HashSet::from([
+                ("b.ts".to_string(), Symbol::Symbol("shared".to_string())),
+                ("c.ts".to_string(), Symbol::Symbol("shared".to_string())),
+            ])
+        );
+    }
+
     #[test]
     fn namespace_import() {
         let analyzed_package = analyze_package(
@@ -213,4 +517,149 @@ mod tests {
         let unused_exports = find_unused_exports(&[analyzed_package]);
         assert_eq!(unused_exports.len(), 0);
     }
+
+    #[test]
+    fn subpath_imports() {
+        let analyzed_package = analyze_package(
+            &PathBuf::from("./tests/subpath-imports/"),
+            &Default::default(),
+            &Default::default(),
+            Default::default(),
+        )
+        .unwrap();
+        assert!(analyzed_package.unresolved_paths.is_empty());
+
+        let unused_exports = find_unused_exports(&[analyzed_package]);
+        assert_eq!(unused_exports.len(), 0);
+    }
+
+    #[test]
+    fn esm_imports() {
+        let path = PathBuf::from("./tests/esm-imports/");
+        let analyzed_package = analyze_package(
+            &path,
+            &try_load_tsconfig(&path).unwrap(),
+            &Default::default(),
+            Default::default(),
+        )
+        .unwrap();
+        assert!(analyzed_package.unresolved_paths.is_empty());
+
+        let unused_exports = find_unused_exports(&[analyzed_package]);
+        assert_eq!(unused_exports.len(), 1);
+        assert_eq!(
+            unused_exports[0].filename,
+            path.join("src/platform.ts").canonicalize().unwrap()
+        );
+    }
+
+    #[test]
+    fn package_exports() {
+        let analyzed_packages = analyze_monorepo("./tests/package-exports/");
+
+        let unused_exports = find_unused_exports(&analyzed_packages)
+            .into_iter()
+            .map(|e| e.symbol)
+            .collect::<HashSet<Symbol>>();
+        assert_eq!(
+            unused_exports,
+            HashSet::from([
+                Symbol::Symbol("unusedValue".to_string()),
+                Symbol::Symbol("helper".to_string())
+            ])
+        );
+    }
+
+    #[test]
+    fn multi_source_declarations() {
+        let analyzed_packages = analyze_monorepo("./tests/multi-source-declarations/");
+
+        let unused_exports = find_unused_exports(&analyzed_packages);
+        assert_eq!(unused_exports.len(), 1);
+        assert_eq!(
+            unused_exports[0].symbol,
+            Symbol::Symbol("unusedA".to_string())
+        );
+
+        // the bundled declaration itself never stands for an import
+        let imported = analyzed_packages
+            .iter()
+            .flat_map(|p| p.modules.values())
+            .flat_map(|m| &m.symbols.imports)
+            .map(|i| i.from.file_name().unwrap().to_str().unwrap())
+            .collect::<HashSet<_>>();
+        assert_eq!(imported, HashSet::from(["a.ts", "b.ts"]));
+    }
+
+    #[test]
+    fn exports_used_locally() {
+        let analyzed_package = analyze_package(
+            &PathBuf::from("./tests/exports-used-locally/"),
+            &Default::default(),
+            &Default::default(),
+            Default::default(),
+        )
+        .unwrap();
+
+        let unused_exports = find_unused_exports(&[analyzed_package])
+            .into_iter()
+            .map(|e| (e.symbol, e.kind))
+            .collect::<HashSet<_>>();
+        assert_eq!(
+            unused_exports,
+            HashSet::from([
+                (
+                    Symbol::Symbol("unused".to_string()),
+                    UnusedExportKind::Unused
+                ),
+                (
+                    Symbol::Symbol("internal".to_string()),
+                    UnusedExportKind::UsedLocally
+                ),
+                (
+                    Symbol::Symbol("renamed".to_string()),
+                    UnusedExportKind::UsedLocally
+                ),
+                // `export default defaultToo` isn't a local use
+                (
+                    Symbol::Symbol("defaultToo".to_string()),
+                    UnusedExportKind::Unused
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn duplicate_exports() {
+        let analyzed_package = analyze_package(
+            &PathBuf::from("./tests/duplicate-exports/"),
+            &Default::default(),
+            &Default::default(),
+            Default::default(),
+        )
+        .unwrap();
+
+        let duplicate_exports = find_duplicate_exports(&[analyzed_package])
+            .into_iter()
+            .map(|e| {
+                (
+                    e.filename.file_name().unwrap().to_str().unwrap().to_owned(),
+                    e.symbol,
+                    match e.kind {
+                        DuplicateExportKind::AmbiguousReexport(origins) => origins.len(),
+                        DuplicateExportKind::NamedAndDefault => 0,
+                    },
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            duplicate_exports,
+            vec![
+                ("button.ts".to_string(), "Button".to_string(), 0),
+                ("index.ts".to_string(), "format".to_string(), 2),
+                ("link.ts".to_string(), "Link".to_string(), 0),
+            ]
+        );
+    }
 }
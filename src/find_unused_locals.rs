@@ -0,0 +1,159 @@
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+use crate::analyze_package::AnalyzedPackage;
+use crate::analyzed_module::AnalyzedModule;
+use crate::find_unused_exports::{Symbol, UnusedExport};
+use crate::module_symbols::{Declaration, Location};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnusedLocal {
+    pub filename: PathBuf,
+    pub name: String,
+    pub location: Location,
+}
+
+/// Find top-level declarations which are neither exported nor reachable from
+/// the module's side effects or its used exports. Declarations referenced only
+/// from unused exports are reported as well, so dead code cascades are visible
+/// in a single run.
+pub fn find_unused_locals(
+    analyzed_packages: &[AnalyzedPackage],
+    unused_exports: &[UnusedExport],
+) -> Vec<UnusedLocal> {
+    let mut unused_exports_by_path: HashMap<&PathBuf, HashSet<&str>> = HashMap::new();
+
+    for unused_export in unused_exports {
+        if let Symbol::Symbol(s) = &unused_export.symbol {
+            unused_exports_by_path
+                .entry(&unused_export.filename)
+                .or_default()
+                .insert(s);
+        }
+    }
+
+    let mut unused_locals = analyzed_packages
+        .iter()
+        .flat_map(|p| p.modules.values())
+        .flat_map(|module| {
+            let unused_exports = unused_exports_by_path
+                .get(&module.path)
+                .cloned()
+                .unwrap_or_default();
+
+            find_unused_module_locals(module, &unused_exports)
+        })
+        .collect::<Vec<_>>();
+
+    unused_locals.sort_by(|a, b| (&a.filename, a.location).cmp(&(&b.filename, b.location)));
+    unused_locals
+}
+
+fn find_unused_module_locals(
+    module: &AnalyzedModule<PathBuf>,
+    unused_exports: &HashSet<&str>,
+) -> Vec<UnusedLocal> {
+    let declarations = &module.symbols.declarations;
+
+    let mut declarations_by_name: HashMap<&str, Vec<&Declaration>> = HashMap::new();
+    for declaration in declarations {
+        declarations_by_name
+            .entry(&declaration.name)
+            .or_default()
+            .push(declaration);
+    }
+
+    let mut pending = module
+        .symbols
+        .top_level_references
+        .iter()
+        .map(String::as_str)
+        .chain(
+            declarations
+                .iter()
+                .filter(|d| {
+                    d.exported_as
+                        .iter()
+                        .any(|e| !unused_exports.contains(e.as_str()))
+                })
+                .map(|d| d.name.as_str()),
+        )
+        .collect::<Vec<_>>();
+    let mut reachable = HashSet::new();
+
+    while let Some(name) = pending.pop() {
+        if !reachable.insert(name) {
+            continue;
+        }
+
+        for declaration in declarations_by_name.get(name).into_iter().flatten() {
+            pending.extend(declaration.references.iter().map(String::as_str));
+        }
+    }
+
+    declarations
+        .iter()
+        .filter(|d| d.exported_as.is_empty() && !reachable.contains(d.name.as_str()))
+        .map(|d| UnusedLocal {
+            filename: module.path.to_owned(),
+            name: d.name.to_owned(),
+            location: d.location,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        find_unused_exports::find_unused_exports, find_unused_locals::find_unused_locals,
+        test_utils::analyze_monorepo,
+    };
+
+    #[test]
+    fn unused_locals() {
+        let analyzed_packages = analyze_monorepo("./tests/unused-locals/");
+
+        let unused_exports = find_unused_exports(&analyzed_packages);
+        let unused_locals = find_unused_locals(&analyzed_packages, &unused_exports)
+            .into_iter()
+            .map(|l| {
+                (
+                    l.filename.file_name().unwrap().to_str().unwrap().to_owned(),
+                    l.name,
+                    l.location.line,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let helpers = |name: &str, line| ("helpers.ts".to_string(), name.to_string(), line);
+
+        assert_eq!(
+            unused_locals,
+            vec![
+                helpers("unusedConstant", 3),
+                helpers("helper", 5),
+                helpers("onlyUsedByUnusedExport", 9),
+                helpers("recursive", 29),
+                helpers("shadowed", 33),
+                ("patterns.ts".to_string(), "second".to_string(), 1),
+                ("patterns.ts".to_string(), "rest".to_string(), 2),
+            ]
+        );
+
+        // used by side effects, in types, in JSX or by the other bindings of a pattern
+        for used in [
+            "usedBySideEffect",
+            "Options",
+            "Button",
+            "title",
+            "first",
+            "kept",
+            "Alias",
+        ] {
+            assert!(
+                unused_locals.iter().all(|(_, name, _)| name != used),
+                "{used}"
+            );
+        }
+    }
+}
@@ -0,0 +1,307 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use crate::analyze_package::AnalyzedPackage;
+use crate::find_unused_exports::{resolve_named_import, Modules, Symbol};
+use crate::module_symbols::{Import, Location, MemberKind, Usage};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnusedMember {
+    pub filename: PathBuf,
+    pub symbol: String,
+    pub member: String,
+    pub location: Location,
+}
+
+// (module, local name of the declaration)
+type DeclarationKey = (PathBuf, String);
+
+#[derive(Default)]
+struct MemberUsages {
+    members: HashSet<(DeclarationKey, String)>,
+
+    // declarations used as a whole (passed around, iterated, indexed dynamically)
+    escaped: HashSet<DeclarationKey>,
+}
+
+/// Find members of exported enums (and optionally `as const` objects) which
+/// are never accessed as `Symbol.Member`. Any other use of the symbol marks all
+/// its members as used.
+pub fn find_unused_members(
+    analyzed_packages: &[AnalyzedPackage],
+    include_const_objects: bool,
+) -> Vec<UnusedMember> {
+    let modules = analyzed_packages
+        .iter()
+        .flat_map(|p| p.modules.clone())
+        .collect::<Modules>();
+
+    let usages = collect_member_usages(&modules);
+
+    let mut unused_members = modules
+        .values()
+        .flat_map(|module| {
+            module
+                .symbols
+                .declarations
+                .iter()
+                .filter(|d| !d.exported_as.is_empty())
+                .map(move |d| (module, d))
+        })
+        .filter(|(module, d)| {
+            !usages
+                .escaped
+                .contains(&(module.path.to_owned(), d.name.to_owned()))
+        })
+        .flat_map(|(module, d)| {
+            let key = (module.path.to_owned(), d.name.to_owned());
+
+            d.members
+                .iter()
+                .filter(|m| match m.kind {
+                    MemberKind::EnumMember => true,
+                    MemberKind::ConstObjectProperty => include_const_objects,
+                    MemberKind::ClassMember => false,
+                })
+                .filter(|m| {
+                    !usages
+                        .members
+                        .contains(&(key.to_owned(), m.name.to_owned()))
+                })
+                .map(|m| UnusedMember {
+                    filename: module.path.to_owned(),
+                    symbol: d.name.to_owned(),
+                    member: m.name.to_owned(),
+                    location: m.location,
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    unused_members.sort_by(|a, b| (&a.filename, a.location).cmp(&(&b.filename, b.location)));
+    unused_members
+}
+
+/// Find public members of exported classes whose name is never accessed as a
+/// property anywhere. Matching is done by name only, so a member is considered
+/// used if any object's property of the same name is accessed. Classes whose
+/// module accesses `this[...]` dynamically are skipped.
+pub fn find_unused_class_members(analyzed_packages: &[AnalyzedPackage]) -> Vec<UnusedMember> {
+    let modules = analyzed_packages
+        .iter()
+        .flat_map(|p| p.modules.values())
+        .collect::<Vec<_>>();
+
+    let accessed_properties = modules
+        .iter()
+        .flat_map(|m| &m.symbols.usages)
+        .filter_map(|usage| match usage {
+            Usage::Property(property)
+            | Usage::Member(_, property)
+            | Usage::NamespaceMember(_, property, _) => Some(property.as_str()),
+            _ => None,
+        })
+        .collect::<HashSet<&str>>();
+
+    let mut unused_members = modules
+        .iter()
+        .filter(|m| !m.symbols.usages.contains(&Usage::DynamicProperty))
+        .flat_map(|module| {
+            module
+                .symbols
+                .declarations
+                .iter()
+                .filter(|d| !d.exported_as.is_empty())
+                .flat_map(move |d| {
+                    d.members
+                        .iter()
+                        .filter(|m| m.kind == MemberKind::ClassMember)
+                        .map(move |m| UnusedMember {
+                            filename: module.path.to_owned(),
+                            symbol: d.name.to_owned(),
+                            member: m.name.to_owned(),
+                            location: m.location,
+                        })
+                })
+        })
+        .filter(|m| !accessed_properties.contains(m.member.as_str()))
+        .collect::<Vec<_>>();
+
+    unused_members.sort_by(|a, b| (&a.filename, a.location).cmp(&(&b.filename, b.location)));
+    unused_members
+}
+
+impl MemberUsages {
+    /// Record accesses of the `local` binding referring to the `targets` declarations.
+    fn record(&mut self, usages: &HashSet<Usage>, local: &str, targets: &HashSet<DeclarationKey>) {
+        for usage in usages {
+            match usage {
+                Usage::Member(symbol, member) if symbol == local => {
+                    for target in targets {
+                        self.members.insert((target.to_owned(), member.to_owned()));
+                    }
+                }
+                Usage::Symbol(symbol) if symbol == local => {
+                    self.escaped.extend(targets.iter().cloned());
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+fn collect_member_usages(modules: &Modules) -> MemberUsages {
+    let mut result = MemberUsages::default();
+
+    for module in modules.values() {
+        let usages = &module.symbols.usages;
+
+        let local_declarations = module
+            .symbols
+            .declarations
+            .iter()
+            .map(|d| d.name.as_str())
+            .collect::<HashSet<&str>>();
+
+        for local in local_declarations {
+            result.record(
+                usages,
+                local,
+                &HashSet::from([(module.path.to_owned(), local.to_owned())]),
+            );
+        }
+
+        for import in &module.symbols.imports {
+            for symbol in &import.symbols {
+                match symbol {
+                    Import::Named(imported, local) => {
+                        let targets = resolve_declarations(imported, &import.from, modules);
+                        result.record(usages, local, &targets);
+                    }
+                    Import::Default(local) => {
+                        let targets = resolve_declarations("default", &import.from, modules);
+                        result.record(usages, local, &targets);
+                    }
+                    Import::Namespace(alias) => {
+                        for usage in usages {
+                            match usage {
+                                Usage::NamespaceMember(symbol, member, current_alias)
+                                    if current_alias == alias =>
+                                {
+                                    for target in
+                                        resolve_declarations(symbol, &import.from, modules)
+                                    {
+                                        result.members.insert((target, member.to_owned()));
+                                    }
+                                }
+                                Usage::Namespace(symbol, current_alias)
+                                    if current_alias == alias =>
+                                {
+                                    result.escaped.extend(resolve_declarations(
+                                        symbol,
+                                        &import.from,
+                                        modules,
+                                    ));
+                                }
+                                _ => {}
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    result
+}
+
+/// Local declarations behind an exported name, following re-exports.
+fn resolve_declarations(symbol: &str, from: &Path, modules: &Modules) -> HashSet<DeclarationKey> {
+    let exports = if symbol == "default" {
+        HashSet::from([(Symbol::Default, from.to_owned())])
+    } else {
+        resolve_named_import(symbol, from, modules)
+    };
+
+    exports
+        .into_iter()
+        .filter_map(|(_, path)| modules.get(&path))
+        .flat_map(|module| {
+            module
+                .symbols
+                .declarations
+                .iter()
+                .filter(|d| d.exported_as.contains(symbol))
+                .map(|d| (module.path.to_owned(), d.name.to_owned()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        find_unused_members::{find_unused_class_members, find_unused_members},
+        test_utils::analyze_monorepo,
+    };
+
+    #[test]
+    fn unused_members() {
+        let analyzed_packages = analyze_monorepo("./tests/unused-members/");
+
+        let unused_members = |include_const_objects| {
+            find_unused_members(&analyzed_packages, include_const_objects)
+                .into_iter()
+                .map(|m| format!("{}.{}", m.symbol, m.member))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(unused_members(false), vec!["Color.Blue", "Status.Inactive"]);
+        assert_eq!(
+            unused_members(true),
+            vec!["Color.Blue", "Status.Inactive", "Sizes.large"]
+        );
+
+        // members accessed through a namespace import, in the declaring module
+        // or of an enum iterated as a whole are used
+        for used in [
+            "Color.Green",
+            "Status.Active",
+            "Direction.Up",
+            "Direction.Down",
+        ] {
+            assert!(!unused_members(true).contains(&used.to_string()), "{used}");
+        }
+    }
+
+    #[test]
+    fn unused_class_members() {
+        let analyzed_packages = analyze_monorepo("./tests/unused-class-members/");
+
+        let unused_members = find_unused_class_members(&analyzed_packages)
+            .into_iter()
+            .map(|m| format!("{}.{}", m.symbol, m.member))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            unused_members,
+            vec![
+                "Service.unusedMethod",
+                "Service.unusedProperty",
+                "Service.name"
+            ]
+        );
+
+        // used through `this`, static, private names, accessed by `this[...]` or
+        // possibly overriding a base class
+        for used in [
+            "Service.helper",
+            "Service.create",
+            "Service.value",
+            "Service.#secret",
+            "Dynamic.unused",
+            "Component.render",
+        ] {
+            assert!(!unused_members.contains(&used.to_string()), "{used}");
+        }
+    }
+}
@@ -0,0 +1,268 @@
+use std::collections::HashSet;
+use std::ffi::{OsStr, OsString};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::analyze_file::analyze_source;
+use crate::analyze_package::{
+    build_path, is_source_file, package_of, update_module, AnalyzeOptions, AnalyzedPackage,
+};
+use crate::analyze_plan::Package;
+use crate::error::{Error, Result};
+use crate::find_unused_exports::{find_unused_exports, UnusedExport};
+
+/// Files of a git repository changed since a ref, committed or not.
+#[derive(Debug, Clone)]
+pub struct GitChanges {
+    root: PathBuf,
+    since: String,
+    pub paths: HashSet<PathBuf>,
+
+    // files which didn't exist at the ref
+    added: HashSet<PathBuf>,
+}
+
+impl GitChanges {
+    /// Changes in the repository containing the path, untracked files count as added.
+    pub fn new(path: &Path, since: &str) -> Result<Self> {
+        let root = git(path, &["rev-parse", "--show-toplevel"])?;
+        let root = PathBuf::from(root.trim())
+            .canonicalize()
+            .map_err(Error::GitUnavailable)?;
+
+        let mut paths = HashSet::new();
+        let mut added = HashSet::new();
+
+        let diff = git(
+            &root,
+            &["diff", "--name-status", "--no-renames", "-z", since, "--"],
+        )?;
+        let mut entries = diff.split('\0').filter(|e| !e.is_empty());
+
+        while let (Some(status), Some(name)) = (entries.next(), entries.next()) {
+            let path = root.join(name);
+            if status == "A" {
+                added.insert(path.to_owned());
+            }
+            paths.insert(path);
+        }
+
+        let untracked = git(&root, &["ls-files", "--others", "--exclude-standard", "-z"])?;
+        for name in untracked.split('\0').filter(|n| !n.is_empty()) {
+            let path = root.join(name);
+            added.insert(path.to_owned());
+            paths.insert(path);
+        }
+
+        Ok(Self {
+            root,
+            since: since.to_owned(),
+            paths,
+            added,
+        })
+    }
+
+    /// Content of the file at the ref, `None` for files added since.
+    pub fn original_source(&self, path: &Path) -> Result<Option<String>> {
+        if self.added.contains(path) {
+            return Ok(None);
+        }
+
+        let Ok(name) = path.strip_prefix(&self.root) else {
+            return Ok(None);
+        };
+
+        // `<ref>:<path>`, the path isn't necessarily UTF-8
+        let mut object = OsString::from(format!("{}:", self.since));
+        object.push(name);
+        git(&self.root, &[OsStr::new("show"), &object]).map(Some)
+    }
+}
+
+/// Unused exports declared in the changed files, or which were still imported
+/// before the changes.
+pub fn find_changed_unused_exports(
+    unused_exports: &[UnusedExport],
+    analyzed_packages: &[AnalyzedPackage],
+    packages: &[Package],
+    options: &AnalyzeOptions,
+    changes: &GitChanges,
+) -> Result<Vec<UnusedExport>> {
+    let original_packages = analyze_original(analyzed_packages, packages, options, changes)?;
+
+    let previously_unused = find_unused_exports(&original_packages)
+        .into_iter()
+        .map(|e| (e.filename, e.symbol))
+        .collect::<HashSet<_>>();
+
+    Ok(unused_exports
+        .iter()
+        .filter(|e| {
+            changes.paths.contains(&e.filename)
+                || !previously_unused.contains(&(e.filename.to_owned(), e.symbol.to_owned()))
+        })
+        .cloned()
+        .collect())
+}
+
+/// The analyzed packages with the changed modules analyzed as they were at the ref.
+fn analyze_original(
+    analyzed_packages: &[AnalyzedPackage],
+    packages: &[Package],
+    options: &AnalyzeOptions,
+    changes: &GitChanges,
+) -> Result<Vec<AnalyzedPackage>> {
+    let mut original_packages = analyzed_packages.to_vec();
+
+    for path in &changes.paths {
+        let Some(index) = package_of(&original_packages, path) else {
+            continue;
+        };
+
+        let analyzed_package = &mut original_packages[index];
+        let tsconfig = packages
+            .iter()
+            .find(|p| p.path == analyzed_package.path)
+            .and_then(|p| p.tsconfig.clone());
+
+        let out_dir = build_path(&analyzed_package.path, &tsconfig);
+        if !is_source_file(path, &options.exclude_patterns, &out_dir) {
+            continue;
+        }
+
+        match changes.original_source(path)? {
+            Some(source) => {
+                // files which didn't parse back then have nothing to compare with
+                if let Some(analyzed_file) = analyze_source(path, source) {
+                    update_module(analyzed_package, path, analyzed_file, &tsconfig, packages)?;
+                }
+            }
+            None => {
+                analyzed_package.modules.remove(path);
+            }
+        }
+    }
+
+    Ok(original_packages)
+}
+
+fn git<S: AsRef<OsStr>>(path: &Path, args: &[S]) -> Result<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(path)
+        .args(args)
+        .output()
+        .map_err(Error::GitUnavailable)?;
+
+    if !output.status.success() {
+        return Err(Error::Git {
+            command: args
+                .iter()
+                .map(|a| a.as_ref().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join(" "),
+            message: String::from_utf8_lossy(&output.stderr).trim().to_owned(),
+        });
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path, process::Command};
+
+    use anyhow::Result;
+
+    use crate::{
+        analyze_package::analyze_package,
+        find_unused_exports::{find_unused_exports, Symbol},
+        git_changes::{find_changed_unused_exports, GitChanges},
+    };
+
+    fn commit(path: &Path) -> Result<()> {
+        for args in [
+            vec!["add", "-A"],
+            vec![
+                "-c",
+                "user.name=test",
+                "-c",
+                "user.email=test@test",
+                "commit",
+                "-qm",
+                "test",
+            ],
+        ] {
+            assert!(Command::new("git")
+                .arg("-C")
+                .arg(path)
+                .args(args)
+                .status()?
+                .success());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn changed_unused_exports() -> Result<()> {
+        let path = std::env::temp_dir().join(format!(
+            "ts-unused-symbols-git-changes-test-{}",
+            std::process::id()
+        ));
+        fs::create_dir_all(path.join("src"))?;
+        fs::write(
+            path.join("src/a.ts"),
+            "export const a = 1;\nexport const b = 2;",
+        )?;
+        fs::write(path.join("src/b.ts"), "import { a } from './a';\na;")?;
+        fs::write(path.join("src/c.ts"), "export const c = 3;")?;
+        let path = path.canonicalize()?;
+
+        assert!(Command::new("git")
+            .arg("init")
+            .arg("-q")
+            .arg(&path)
+            .status()?
+            .success());
+        commit(&path)?;
+
+        fs::write(path.join("src/b.ts"), "export const d = 4;")?;
+        fs::write(path.join("src/e.ts"), "export const e = 5;")?;
+
+        let analyzed_packages = vec![analyze_package(
+            &path,
+            &Default::default(),
+            &Default::default(),
+            Default::default(),
+        )?];
+        let changes = GitChanges::new(&path, "HEAD")?;
+
+        let unused_exports = find_changed_unused_exports(
+            &find_unused_exports(&analyzed_packages),
+            &analyzed_packages,
+            Default::default(),
+            &Default::default(),
+            &changes,
+        )?;
+        let mut unused_exports = unused_exports
+            .iter()
+            .map(|e| e.symbol.to_owned())
+            .collect::<Vec<_>>();
+        unused_exports.sort();
+
+        // `b` and `c` were unused before the changes and are declared in untouched files
+        assert_eq!(
+            unused_exports,
+            vec![
+                Symbol::Symbol("a".to_string()),
+                Symbol::Symbol("d".to_string()),
+                Symbol::Symbol("e".to_string()),
+            ]
+        );
+
+        fs::remove_dir_all(&path)?;
+
+        Ok(())
+    }
+}
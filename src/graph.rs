@@ -0,0 +1,434 @@
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+use serde::Serialize;
+
+use crate::analyze_package::{package_of, AnalyzedPackage};
+use crate::analyze_plan::Package;
+use crate::find_unused_exports::{Symbol, UnusedExport};
+use crate::module_symbols::Export;
+
+/// Resolved import graph of the modules, or of the packages they belong to.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Graph {
+    pub nodes: Vec<Node>,
+    pub edges: Vec<Edge>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Node {
+    // module path relative to the analyzed root, or package name
+    pub name: String,
+
+    // package of the module, none for package nodes
+    pub package: Option<String>,
+
+    // prefixed with the module path for package nodes
+    pub unused_exports: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct Edge {
+    pub from: String,
+    pub to: String,
+
+    // all the imports behind the edge are `import type`
+    pub type_only: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GraphOptions {
+    // one node per package instead of per module
+    pub collapse_packages: bool,
+
+    // names of the packages to keep, all when empty
+    pub packages: Vec<String>,
+
+    // keep only the modules matching any of the patterns, all when empty
+    pub path_patterns: Vec<Regex>,
+
+    // keep only the modules at most this many imports away from the roots
+    pub depth: Option<usize>,
+
+    // roots of the depth filter, the package entry points when empty
+    pub roots: Vec<PathBuf>,
+}
+
+/// Build the import graph of the analyzed packages, module names are relative
+/// to the root path.
+pub fn build_graph(
+    analyzed_packages: &[AnalyzedPackage],
+    packages: &[Package],
+    unused_exports: &[UnusedExport],
+    root: &Path,
+    options: &GraphOptions,
+) -> Graph {
+    let root = &root.canonicalize().unwrap_or_else(|_| root.to_owned());
+
+    let modules = analyzed_packages
+        .iter()
+        .flat_map(|p| p.modules.values())
+        .map(|m| (m.path.as_path(), m))
+        .collect::<HashMap<_, _>>();
+
+    let package_names = analyzed_packages
+        .iter()
+        .map(|analyzed_package| {
+            packages
+                .iter()
+                .find(|p| p.path == analyzed_package.path)
+                .map(|p| p.package_json.name.to_owned())
+                .unwrap_or_else(|| name_of(&analyzed_package.path, root))
+        })
+        .collect::<Vec<_>>();
+    let package_name = |path: &Path| {
+        package_of(analyzed_packages, path).map(|index| package_names[index].to_owned())
+    };
+
+    // (importer, imported) -> all the imports are type-only
+    let mut module_edges: BTreeMap<(&Path, &Path), bool> = BTreeMap::new();
+
+    for module in modules.values() {
+        let imports = module
+            .symbols
+            .imports
+            .iter()
+            .map(|i| (&i.from, i.type_only));
+        let reexports = module.symbols.reexports.iter().map(|r| (&r.from, false));
+        let all_exports = module.symbols.exports.iter().filter_map(|e| match e {
+            Export::AllFrom(from) => Some((from, false)),
+            _ => None,
+        });
+
+        for (from, type_only) in imports.chain(reexports).chain(all_exports) {
+            if modules.contains_key(from.as_path()) && from != &module.path {
+                let entry = module_edges
+                    .entry((&module.path, from))
+                    .or_insert(type_only);
+                *entry &= type_only;
+            }
+        }
+    }
+
+    let within_depth = options.depth.map(|depth| {
+        let roots = if options.roots.is_empty() {
+            analyzed_packages
+                .iter()
+                .flat_map(|p| p.entry_points.iter().cloned())
+                .collect()
+        } else {
+            options.roots.clone()
+        };
+
+        reachable(&roots, &module_edges, depth)
+    });
+
+    let kept = modules
+        .keys()
+        .copied()
+        .filter(|path| {
+            options.packages.is_empty()
+                || package_name(path).is_some_and(|name| options.packages.contains(&name))
+        })
+        .filter(|path| {
+            options.path_patterns.is_empty()
+                || options
+                    .path_patterns
+                    .iter()
+                    .any(|r| r.is_match(&path.to_string_lossy()))
+        })
+        .filter(|path| within_depth.as_ref().is_none_or(|w| w.contains(*path)))
+        .collect::<HashSet<_>>();
+
+    let mut unused_by_path: HashMap<&Path, Vec<String>> = HashMap::new();
+    for unused_export in unused_exports {
+        unused_by_path
+            .entry(&unused_export.filename)
+            .or_default()
+            .push(match &unused_export.symbol {
+                Symbol::Symbol(s) => s.to_owned(),
+                Symbol::Default => "default".to_string(),
+            });
+    }
+
+    let mut nodes: BTreeMap<String, Node> = BTreeMap::new();
+    let mut edges: BTreeMap<(String, String), bool> = BTreeMap::new();
+
+    let node_name = |path: &Path| {
+        if options.collapse_packages {
+            package_name(path).unwrap_or_else(|| name_of(path, root))
+        } else {
+            name_of(path, root)
+        }
+    };
+
+    for path in &kept {
+        let name = node_name(path);
+        let node = nodes.entry(name.to_owned()).or_insert_with(|| Node {
+            name,
+            package: (!options.collapse_packages)
+                .then(|| package_name(path))
+                .flatten(),
+            unused_exports: vec![],
+        });
+
+        for symbol in unused_by_path.get(path).into_iter().flatten() {
+            node.unused_exports.push(if options.collapse_packages {
+                format!("{}: {symbol}", name_of(path, root))
+            } else {
+                symbol.to_owned()
+            });
+        }
+    }
+
+    for ((from, to), type_only) in module_edges {
+        if !kept.contains(from) || !kept.contains(to) {
+            continue;
+        }
+
+        let (from, to) = (node_name(from), node_name(to));
+        if from != to {
+            let entry = edges.entry((from, to)).or_insert(type_only);
+            *entry &= type_only;
+        }
+    }
+
+    Graph {
+        nodes: nodes
+            .into_values()
+            .map(|mut node| {
+                node.unused_exports.sort();
+                node
+            })
+            .collect(),
+        edges: edges
+            .into_iter()
+            .map(|((from, to), type_only)| Edge {
+                from,
+                to,
+                type_only,
+            })
+            .collect(),
+    }
+}
+
+impl Graph {
+    /// Graphviz DOT, modules with unused exports are filled and type-only
+    /// imports dashed.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph {\n");
+
+        for node in &self.nodes {
+            if node.unused_exports.is_empty() {
+                dot.push_str(&format!("    {};\n", dot_string(&node.name)));
+            } else {
+                dot.push_str(&format!(
+                    "    {} [style=filled, fillcolor=\"#ffd7a8\", tooltip={}];\n",
+                    dot_string(&node.name),
+                    dot_string(&format!(
+                        "unused exports: {}",
+                        node.unused_exports.join(", ")
+                    ))
+                ));
+            }
+        }
+
+        for edge in &self.edges {
+            dot.push_str(&format!(
+                "    {} -> {}{};\n",
+                dot_string(&edge.from),
+                dot_string(&edge.to),
+                if edge.type_only {
+                    " [style=dashed]"
+                } else {
+                    ""
+                }
+            ));
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Mermaid flowchart, modules with unused exports are in the `unused` class
+    /// and type-only imports dotted.
+    pub fn to_mermaid(&self) -> String {
+        let mut mermaid = String::from("flowchart LR\n");
+
+        let ids = self
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| (node.name.as_str(), format!("n{index}")))
+            .collect::<HashMap<_, _>>();
+
+        for node in &self.nodes {
+            mermaid.push_str(&format!(
+                "    {}[\"{}\"]\n",
+                ids[node.name.as_str()],
+                node.name.replace('"', "#quot;")
+            ));
+        }
+
+        for edge in &self.edges {
+            mermaid.push_str(&format!(
+                "    {} {} {}\n",
+                ids[edge.from.as_str()],
+                if edge.type_only { "-.->" } else { "-->" },
+                ids[edge.to.as_str()]
+            ));
+        }
+
+        let unused = self
+            .nodes
+            .iter()
+            .filter(|n| !n.unused_exports.is_empty())
+            .map(|n| ids[n.name.as_str()].as_str())
+            .collect::<Vec<_>>();
+
+        if !unused.is_empty() {
+            mermaid.push_str("    classDef unused fill:#ffd7a8\n");
+            mermaid.push_str(&format!("    class {} unused\n", unused.join(",")));
+        }
+
+        mermaid
+    }
+}
+
+/// Modules at most `depth` imports away from the roots.
+fn reachable<'a>(
+    roots: &[PathBuf],
+    edges: &BTreeMap<(&'a Path, &'a Path), bool>,
+    depth: usize,
+) -> HashSet<PathBuf> {
+    let mut imported: HashMap<&Path, Vec<&Path>> = HashMap::new();
+    for (from, to) in edges.keys() {
+        imported.entry(*from).or_default().push(*to);
+    }
+
+    let mut result = roots.iter().cloned().collect::<HashSet<_>>();
+    let mut queue = roots
+        .iter()
+        .map(|r| (r.to_owned(), 0))
+        .collect::<VecDeque<_>>();
+
+    while let Some((path, distance)) = queue.pop_front() {
+        if distance == depth {
+            continue;
+        }
+
+        for next in imported.get(path.as_path()).into_iter().flatten() {
+            if result.insert(next.to_path_buf()) {
+                queue.push_back((next.to_path_buf(), distance + 1));
+            }
+        }
+    }
+
+    result
+}
+
+/// Quoted DOT identifier, only `"` and `\` need escaping.
+fn dot_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn name_of(path: &Path, root: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::{
+        analyze_package::analyze_packages,
+        analyze_plan::prepare_analyze_plan,
+        find_unused_exports::{Symbol, UnusedExport, UnusedExportKind},
+        graph::{build_graph, dot_string, Edge, GraphOptions},
+    };
+
+    #[test]
+    fn dot_strings() {
+        assert_eq!(dot_string("src/a.ts"), "\"src/a.ts\"");
+        assert_eq!(dot_string("src\\\"é\n.ts"), "\"src\\\\\\\"é\n.ts\"");
+    }
+
+    #[test]
+    fn module_and_package_graphs() {
+        let path = PathBuf::from("./tests/dependents").canonicalize().unwrap();
+        let analyze_plan = prepare_analyze_plan(&path).unwrap();
+        let analyzed_packages =
+            analyze_packages(&analyze_plan.packages, &Default::default()).unwrap();
+
+        let unused_exports = vec![UnusedExport {
+            filename: path.join("packages/lib/src/format.ts"),
+            symbol: Symbol::Symbol("parse".to_string()),
+            kind: UnusedExportKind::Unused,
+        }];
+        let edge = |from: &str, to: &str| Edge {
+            from: from.to_string(),
+            to: to.to_string(),
+            type_only: false,
+        };
+
+        let graph = build_graph(
+            &analyzed_packages,
+            &analyze_plan.packages,
+            &unused_exports,
+            &path,
+            &GraphOptions {
+                depth: Some(1),
+                roots: vec![path.join("packages/app/src/main.ts")],
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            graph.edges,
+            vec![edge("packages/app/src/main.ts", "packages/app/src/app.ts")]
+        );
+        assert_eq!(graph.nodes[0].package, Some("app".to_string()));
+
+        let graph = build_graph(
+            &analyzed_packages,
+            &analyze_plan.packages,
+            &unused_exports,
+            &path,
+            &GraphOptions {
+                collapse_packages: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(graph.edges, vec![edge("app", "lib")]);
+        assert_eq!(
+            graph.to_mermaid(),
+            "flowchart LR\n    n0[\"app\"]\n    n1[\"lib\"]\n    n0 --> n1\n    classDef unused fill:#ffd7a8\n    class n1 unused\n"
+        );
+
+        let graph = build_graph(
+            &analyzed_packages,
+            &analyze_plan.packages,
+            &unused_exports,
+            &path,
+            &GraphOptions {
+                packages: vec!["lib".to_string()],
+                path_patterns: vec![regex::Regex::new("format|index").unwrap()],
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            graph.to_dot(),
+            [
+                "digraph {",
+                "    \"packages/lib/src/format.ts\" [style=filled, fillcolor=\"#ffd7a8\", tooltip=\"unused exports: parse\"];",
+                "    \"packages/lib/src/index.ts\";",
+                "    \"packages/lib/src/index.ts\" -> \"packages/lib/src/format.ts\";",
+                "}\n",
+            ]
+            .join("\n")
+        );
+    }
+}
@@ -0,0 +1,65 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+
+use crate::error::{Error, Result};
+
+/// Load a JSON file which may have comments and trailing commas, like
+/// tsconfig.json does, `None` if it doesn't exist.
+pub fn try_load_json_file<T: DeserializeOwned>(path: &Path) -> Result<Option<T>> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(error) if error.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(source) => {
+            return Err(Error::Io {
+                path: path.to_owned(),
+                source,
+            })
+        }
+    };
+
+    json5::from_str(&content)
+        .map(Some)
+        .map_err(|error| Error::InvalidJson {
+            path: path.to_owned(),
+            message: error.to_string(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use serde_json::{json, Value};
+
+    use crate::{error::Error, json_file::try_load_json_file};
+
+    #[test]
+    fn json_with_comments() {
+        let path = std::env::temp_dir().join(format!(
+            "ts-unused-symbols-json-file-test-{}.json",
+            std::process::id()
+        ));
+
+        fs::write(
+            &path,
+            "{\n  // compiler options\n  \"compilerOptions\": { \"outDir\": \"dist\", },\n  /* trailing */\n}",
+        )
+        .unwrap();
+        assert_eq!(
+            try_load_json_file::<Value>(&path).unwrap(),
+            Some(json!({ "compilerOptions": { "outDir": "dist" } }))
+        );
+
+        fs::write(&path, "{ \"name\": ").unwrap();
+        assert!(matches!(
+            try_load_json_file::<Value>(&path),
+            Err(Error::InvalidJson { .. })
+        ));
+
+        fs::remove_file(&path).unwrap();
+        assert_eq!(try_load_json_file::<Value>(&path).unwrap(), None);
+    }
+}
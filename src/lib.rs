@@ -0,0 +1,98 @@
+//! Find unused exports, locals, class members and dependencies in typescript
+//! packages and monorepos.
+//!
+//! ```no_run
+//! use std::path::Path;
+//!
+//! use ts_unused_symbols::{analyze_packages, find_unused_exports, prepare_analyze_plan, AnalyzeOptions};
+//!
+//! let analyze_plan = prepare_analyze_plan(Path::new("."))?;
+//! let options = AnalyzeOptions::builder().build();
+//! let analyzed_packages = analyze_packages(&analyze_plan.packages, &options)?;
+//!
+//! for unused_export in find_unused_exports(&analyzed_packages) {
+//!     println!("{:?}", unused_export);
+//! }
+//! # Ok::<(), ts_unused_symbols::Error>(())
+//! ```
+
+mod analysis_cache;
+mod analyze_file;
+mod analyze_package;
+mod analyze_plan;
+mod analyze_symbols_usage;
+mod analyzed_module;
+mod declaration_map;
+mod error;
+mod explain;
+mod find_dependents;
+mod find_import_cycles;
+mod find_redundant_reexports;
+mod find_type_only;
+mod find_unlisted_dependencies;
+mod find_unused_dependencies;
+mod find_unused_exports;
+mod find_unused_locals;
+mod find_unused_members;
+mod git_changes;
+mod graph;
+mod json_file;
+mod module_symbols;
+mod package_exports;
+mod package_json;
+mod resolve_import_path;
+mod source_map;
+mod suppressions;
+#[cfg(test)]
+mod test_utils;
+mod tsconfig;
+
+pub use analysis_cache::AnalysisCache;
+pub use analyze_file::{analyze_file, analyze_source};
+pub use analyze_package::{
+    analyze_package, analyze_packages, AnalyzeOptions, AnalyzeOptionsBuilder, AnalyzedPackage,
+    ImportsByKind,
+};
+pub use analyze_plan::{prepare_analyze_plan, AnalyzePlan, Package};
+pub use analyzed_module::AnalyzedModule;
+pub use error::{Error, Result};
+pub use explain::{explain_export, Explanation, Importer, ImporterKind};
+pub use find_dependents::{find_dependents, Dependent};
+pub use find_import_cycles::{find_module_cycles, find_package_cycles, ImportCycle};
+pub use find_redundant_reexports::{
+    find_dead_barrels, find_redundant_reexports, RedundantReexport,
+};
+pub use find_type_only::{
+    find_type_only_exports, find_type_only_imports, TypeOnlyExport, TypeOnlyImport,
+};
+pub use find_unlisted_dependencies::{find_unlisted_dependencies, UnlistedDependency};
+pub use find_unused_dependencies::{
+    find_unused_dependencies, DependencyKind, DependencyOptions, UnusedDependency,
+};
+pub use find_unused_exports::{
+    find_duplicate_exports, find_unused_exports, DuplicateExport, DuplicateExportKind, Symbol,
+    UnusedExport, UnusedExportKind,
+};
+pub use find_unused_locals::{find_unused_locals, UnusedLocal};
+pub use find_unused_members::{find_unused_class_members, find_unused_members, UnusedMember};
+pub use git_changes::{find_changed_unused_exports, GitChanges};
+pub use graph::{build_graph, Edge, Graph, GraphOptions, Node};
+pub use module_symbols::{
+    Declaration, Export, FindingKind, Import, ImportedSymbol, Location, Member, MemberKind,
+    ModuleReference, ModuleSymbols, Reexport, ReexportKind, Suppression, SuppressionKind,
+    SymbolKind, Usage,
+};
+pub use package_json::PackageJson;
+pub use resolve_import_path::ImportKind;
+pub use suppressions::{StaleSuppression, Suppressions};
+pub use tsconfig::{TsConfig, TsConfigCompilerOptions};
+
+/// Incremental analysis behind the watch mode and the language server, not
+/// part of the stable API.
+#[doc(hidden)]
+pub mod incremental {
+    pub use crate::analyze_file::syntax;
+    pub use crate::analyze_package::{
+        build_path, is_source_file, package_of, reanalyze_modules, update_module,
+    };
+}
@@ -0,0 +1,586 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::Result;
+use lsp_server::{
+    Connection, ExtractError, Message, Notification as ServerNotification, Request, Response,
+};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, DidSaveTextDocument,
+    Notification, PublishDiagnostics,
+};
+use lsp_types::request::{CodeActionRequest, Request as _};
+use lsp_types::{
+    CodeAction, CodeActionKind, CodeActionOrCommand, CodeActionParams,
+    CodeActionProviderCapability, Diagnostic, DiagnosticSeverity, DiagnosticTag, InitializeParams,
+    Position, PublishDiagnosticsParams, Range, ServerCapabilities, TextDocumentSyncCapability,
+    TextDocumentSyncKind, TextEdit, Url, WorkspaceEdit,
+};
+use swc_common::sync::Lrc;
+use swc_common::{BytePos, FileName, SourceMap, Spanned};
+use swc_ecma_ast::{Decl, EsVersion, ModuleDecl, ModuleItem, Pat, TsModuleName};
+use swc_ecma_parser::parse_file_as_module;
+
+use ts_unused_symbols::incremental::{package_of, reanalyze_modules, syntax, update_module};
+use ts_unused_symbols::{
+    analyze_packages, analyze_source, find_unused_exports, prepare_analyze_plan, AnalyzeOptions,
+    AnalyzedPackage, Package, Suppressions, Symbol, UnusedExportKind,
+};
+
+use crate::is_ignored;
+
+const SOURCE: &str = "ts-unused-symbols";
+
+// edits are analyzed once the typing pauses for this long
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+struct Server {
+    connection: Connection,
+    options: AnalyzeOptions,
+    packages: Vec<Package>,
+    analyzed_packages: Vec<AnalyzedPackage>,
+
+    // content of the documents open in the editor
+    documents: HashMap<PathBuf, String>,
+
+    // files changed since they were last analyzed
+    pending: HashSet<PathBuf>,
+
+    // files with diagnostics published last time, to clear them when fixed
+    published: HashSet<PathBuf>,
+}
+
+/// Serve unused export diagnostics and fixes over stdio until the client
+/// shuts the server down.
+pub fn run(options: AnalyzeOptions, default_root: PathBuf) -> Result<()> {
+    let (connection, io_threads) = Connection::stdio();
+
+    let capabilities = ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
+        ..Default::default()
+    };
+
+    let params = connection.initialize(serde_json::to_value(capabilities)?)?;
+    let params: InitializeParams = serde_json::from_value(params)?;
+
+    #[allow(deprecated)]
+    let root = params
+        .workspace_folders
+        .and_then(|folders| folders.into_iter().next())
+        .map(|folder| folder.uri)
+        .or(params.root_uri)
+        .and_then(|uri| uri.to_file_path().ok())
+        .unwrap_or(default_root);
+
+    let analyze_plan = prepare_analyze_plan(&root)?;
+
+    // stdout carries the protocol messages
+    for skipped_path in &analyze_plan.skipped_paths {
+        eprintln!("Package not found in {skipped_path:?}, skipping...");
+    }
+
+    let packages = analyze_plan.packages;
+    let analyzed_packages = analyze_packages(&packages, &options)?;
+
+    for analyzed_package in &analyzed_packages {
+        for (path, message) in &analyzed_package.unparsed_files {
+            eprintln!("Failed to parse {path:?}: {message}, skipping...");
+        }
+    }
+
+    let mut server = Server {
+        connection,
+        options,
+        packages,
+        analyzed_packages,
+        documents: HashMap::new(),
+        pending: HashSet::new(),
+        published: HashSet::new(),
+    };
+
+    server.publish_diagnostics()?;
+    server.serve()?;
+
+    drop(server);
+    io_threads.join()?;
+
+    Ok(())
+}
+
+impl Server {
+    fn serve(&mut self) -> Result<()> {
+        loop {
+            let message = if self.pending.is_empty() {
+                match self.connection.receiver.recv() {
+                    Ok(message) => message,
+                    Err(_) => return Ok(()),
+                }
+            } else {
+                match self.connection.receiver.recv_timeout(DEBOUNCE) {
+                    Ok(message) => message,
+                    Err(error) if error.is_timeout() => {
+                        self.analyze_pending()?;
+                        continue;
+                    }
+                    Err(_) => return Ok(()),
+                }
+            };
+
+            match message {
+                Message::Request(request) => {
+                    if self.connection.handle_shutdown(&request)? {
+                        return Ok(());
+                    }
+
+                    self.handle_request(request)?;
+                }
+                Message::Notification(notification) => self.handle_notification(notification)?,
+                Message::Response(_) => {}
+            }
+        }
+    }
+
+    fn handle_request(&mut self, request: Request) -> Result<()> {
+        let id = request.id.clone();
+
+        let response = match request.method.as_str() {
+            CodeActionRequest::METHOD => {
+                match request.extract::<CodeActionParams>(CodeActionRequest::METHOD) {
+                    Ok((id, params)) => Response::new_ok(id, self.code_actions(params)),
+                    Err(error) => Response::new_err(
+                        id,
+                        lsp_server::ErrorCode::InvalidParams as i32,
+                        error.to_string(),
+                    ),
+                }
+            }
+            _ => Response::new_err(
+                request.id,
+                lsp_server::ErrorCode::MethodNotFound as i32,
+                format!("Unsupported request {}", request.method),
+            ),
+        };
+
+        self.connection.sender.send(Message::Response(response))?;
+        Ok(())
+    }
+
+    fn handle_notification(&mut self, notification: ServerNotification) -> Result<()> {
+        let method = notification.method.clone();
+
+        match self.document_change(notification) {
+            Ok(Some((path, is_change))) => {
+                self.pending.insert(path);
+
+                // edits wait for the typing to pause, see `serve`
+                if is_change {
+                    Ok(())
+                } else {
+                    self.analyze_pending()
+                }
+            }
+            Ok(None) => Ok(()),
+            Err(error) => {
+                eprintln!("Ignoring malformed {method} notification: {error}");
+                Ok(())
+            }
+        }
+    }
+
+    /// Track the content of the document, the path of the changed file and
+    /// whether it's only an edit are returned.
+    fn document_change(
+        &mut self,
+        notification: ServerNotification,
+    ) -> std::result::Result<Option<(PathBuf, bool)>, ExtractError<ServerNotification>> {
+        let is_close = notification.method == DidCloseTextDocument::METHOD;
+        let is_change = notification.method == DidChangeTextDocument::METHOD;
+
+        let (uri, text) = match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params = notification
+                    .extract::<lsp_types::DidOpenTextDocumentParams>(DidOpenTextDocument::METHOD)?;
+                (params.text_document.uri, Some(params.text_document.text))
+            }
+            DidChangeTextDocument::METHOD => {
+                let params = notification.extract::<lsp_types::DidChangeTextDocumentParams>(
+                    DidChangeTextDocument::METHOD,
+                )?;
+                // full synchronization, the last change holds the whole document
+                let text = params.content_changes.into_iter().last().map(|c| c.text);
+                (params.text_document.uri, text)
+            }
+            DidSaveTextDocument::METHOD => {
+                let params = notification
+                    .extract::<lsp_types::DidSaveTextDocumentParams>(DidSaveTextDocument::METHOD)?;
+                (params.text_document.uri, None)
+            }
+            DidCloseTextDocument::METHOD => {
+                let params = notification.extract::<lsp_types::DidCloseTextDocumentParams>(
+                    DidCloseTextDocument::METHOD,
+                )?;
+                (params.text_document.uri, None)
+            }
+            _ => return Ok(None),
+        };
+
+        let Ok(path) = uri.to_file_path() else {
+            return Ok(None);
+        };
+        let path = path.canonicalize().unwrap_or(path);
+
+        if is_close {
+            self.documents.remove(&path);
+        } else if let Some(text) = text {
+            self.documents.insert(path.to_owned(), text);
+        }
+
+        Ok(Some((path, is_change)))
+    }
+
+    fn analyze_pending(&mut self) -> Result<()> {
+        for path in std::mem::take(&mut self.pending) {
+            self.update(&path)?;
+        }
+
+        self.publish_diagnostics()
+    }
+
+    /// Analyze the module again from the open document, or from disk.
+    fn update(&mut self, path: &Path) -> Result<()> {
+        let Some(index) = package_of(&self.analyzed_packages, path) else {
+            return Ok(());
+        };
+
+        let analyzed_package = &mut self.analyzed_packages[index];
+        let tsconfig = self
+            .packages
+            .iter()
+            .find(|p| p.path == analyzed_package.path)
+            .and_then(|p| p.tsconfig.clone());
+
+        let updated = match self.documents.get(path) {
+            // documents which don't parse while being edited keep the last analysis
+            Some(text) => match analyze_source(path, text.to_owned()) {
+                Some(analyzed_file) => update_module(
+                    analyzed_package,
+                    path,
+                    analyzed_file,
+                    &tsconfig,
+                    &self.packages,
+                ),
+                None => Ok(()),
+            },
+            None => reanalyze_modules(
+                analyzed_package,
+                &HashSet::from([path.to_owned()]),
+                &tsconfig,
+                &self.options,
+                &self.packages,
+            ),
+        };
+
+        Ok(updated?)
+    }
+
+    fn publish_diagnostics(&mut self) -> Result<()> {
+        let mut diagnostics = self.diagnostics();
+
+        // files fixed since the last time get an empty list
+        for path in self.published.drain() {
+            diagnostics.entry(path).or_default();
+        }
+
+        for (path, diagnostics) in diagnostics {
+            if !diagnostics.is_empty() {
+                self.published.insert(path.to_owned());
+            }
+
+            let Ok(uri) = Url::from_file_path(&path) else {
+                continue;
+            };
+
+            let params = PublishDiagnosticsParams::new(uri, diagnostics, None);
+            self.connection
+                .sender
+                .send(Message::Notification(ServerNotification::new(
+                    PublishDiagnostics::METHOD.to_string(),
+                    params,
+                )))?;
+        }
+
+        Ok(())
+    }
+
+    fn diagnostics(&self) -> HashMap<PathBuf, Vec<Diagnostic>> {
+        let modules = self
+            .analyzed_packages
+            .iter()
+            .flat_map(|p| &p.modules)
+            .collect::<HashMap<_, _>>();
+
+        let mut result: HashMap<PathBuf, Vec<Diagnostic>> = HashMap::new();
+        let mut suppressions =
+            Suppressions::new(&self.analyzed_packages, self.options.suppression_tags());
+
+        for unused_export in find_unused_exports(&self.analyzed_packages) {
+            if is_ignored(&unused_export.filename, self.options.ignore_patterns())
+                || suppressions.suppresses_export(&unused_export)
+            {
+                continue;
+            }
+
+            let symbol = match &unused_export.symbol {
+                Symbol::Symbol(s) => s.as_str(),
+                Symbol::Default => "default",
+            };
+
+            // the declaration's name, or the beginning of the file
+            let range = modules
+                .get(&unused_export.filename)
+                .and_then(|m| {
+                    m.symbols
+                        .declarations
+                        .iter()
+                        .find(|d| d.exported_as.contains(symbol))
+                })
+                .map(|d| {
+                    // swc counts the columns in UTF-16 code units as clients do
+                    let start =
+                        Position::new(d.location.line as u32 - 1, d.location.column as u32 - 1);
+                    let end = Position::new(
+                        start.line,
+                        start.character + d.name.encode_utf16().count() as u32,
+                    );
+                    Range::new(start, end)
+                })
+                .unwrap_or_default();
+
+            let (message, severity) = match unused_export.kind {
+                UnusedExportKind::Unused => (
+                    format!("`{symbol}` is exported but never imported"),
+                    DiagnosticSeverity::WARNING,
+                ),
+                UnusedExportKind::UsedLocally => (
+                    format!("`{symbol}` is exported but only used in this module"),
+                    DiagnosticSeverity::HINT,
+                ),
+            };
+
+            result
+                .entry(unused_export.filename.to_owned())
+                .or_default()
+                .push(Diagnostic {
+                    range,
+                    severity: Some(severity),
+                    source: Some(SOURCE.to_string()),
+                    message,
+                    tags: (unused_export.kind == UnusedExportKind::Unused)
+                        .then(|| vec![DiagnosticTag::UNNECESSARY]),
+                    data: Some(serde_json::Value::String(symbol.to_owned())),
+                    ..Default::default()
+                });
+        }
+
+        result
+    }
+
+    /// Content of the open document, or of the file on disk.
+    fn source(&self, path: &Path) -> Option<String> {
+        self.documents
+            .get(path)
+            .cloned()
+            .or_else(|| fs::read_to_string(path).ok())
+    }
+
+    fn code_actions(&self, params: CodeActionParams) -> Vec<CodeActionOrCommand> {
+        let uri = params.text_document.uri;
+        let Ok(path) = uri.to_file_path() else {
+            return vec![];
+        };
+        let path = path.canonicalize().unwrap_or(path);
+
+        let Some(source) = self.source(&path) else {
+            return vec![];
+        };
+
+        params
+            .context
+            .diagnostics
+            .into_iter()
+            .filter(|d| d.source.as_deref() == Some(SOURCE))
+            .flat_map(|diagnostic| {
+                let symbol = diagnostic
+                    .data
+                    .as_ref()
+                    .and_then(|d| d.as_str())
+                    .unwrap_or_default();
+
+                let Some(edits) = export_edits(&path, &source, symbol) else {
+                    return vec![];
+                };
+
+                [
+                    (
+                        format!("Remove `export` from `{symbol}`"),
+                        edits.remove_export,
+                    ),
+                    (format!("Delete `{symbol}`"), edits.delete_declaration),
+                ]
+                .into_iter()
+                .map(|(title, range)| {
+                    CodeActionOrCommand::CodeAction(CodeAction {
+                        title,
+                        kind: Some(CodeActionKind::QUICKFIX),
+                        diagnostics: Some(vec![diagnostic.clone()]),
+                        edit: Some(WorkspaceEdit::new(HashMap::from([(
+                            uri.clone(),
+                            vec![TextEdit::new(range, String::new())],
+                        )]))),
+                        ..Default::default()
+                    })
+                })
+                .collect()
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct ExportEdits {
+    // `export ` in front of the declaration
+    remove_export: Range,
+
+    // the whole exported declaration with the line break after it
+    delete_declaration: Range,
+}
+
+/// Ranges to edit to drop the `export` of the declaration exported as the
+/// symbol, `None` unless a single name is declared by an `export <declaration>`.
+fn export_edits(path: &Path, source: &str, symbol: &str) -> Option<ExportEdits> {
+    let cm: Lrc<SourceMap> = Default::default();
+    let fm = cm.new_source_file(
+        Lrc::new(FileName::Real(path.to_owned())),
+        source.to_string(),
+    );
+    let module =
+        parse_file_as_module(&fm, syntax(path), EsVersion::EsNext, None, &mut vec![]).ok()?;
+
+    let export = module.body.iter().find_map(|item| match item {
+        ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => {
+            let name = match &export.decl {
+                Decl::Class(c) => Some(&c.ident.sym),
+                Decl::Fn(f) => Some(&f.ident.sym),
+                Decl::TsEnum(e) => Some(&e.id.sym),
+                Decl::TsInterface(i) => Some(&i.id.sym),
+                Decl::TsTypeAlias(t) => Some(&t.id.sym),
+                Decl::TsModule(m) => match &m.id {
+                    TsModuleName::Ident(i) => Some(&i.sym),
+                    TsModuleName::Str(_) => None,
+                },
+                Decl::Var(v) => match v.decls.as_slice() {
+                    [declarator] => match &declarator.name {
+                        Pat::Ident(i) => Some(&i.id.sym),
+                        _ => None,
+                    },
+                    _ => None,
+                },
+                Decl::Using(_) => None,
+            };
+
+            name.filter(|name| *name == symbol).map(|_| export)
+        }
+        _ => None,
+    })?;
+
+    // columns are in UTF-16 code units
+    let position = |pos: BytePos| {
+        let loc = cm.lookup_char_pos(pos);
+        Position::new(loc.line as u32 - 1, loc.col.0 as u32)
+    };
+
+    let start = position(export.span.lo);
+    let end = position(export.span.hi);
+
+    // take the line break too when nothing else follows on the line
+    let rest_of_line = source
+        .lines()
+        .nth(end.line as usize)
+        .map(|line| {
+            let mut column = 0;
+            line.chars()
+                .skip_while(|c| {
+                    let skip = column < end.character as usize;
+                    column += c.len_utf16();
+                    skip
+                })
+                .collect::<String>()
+        })
+        .unwrap_or_default();
+    let end = if rest_of_line.trim().is_empty() {
+        Position::new(end.line + 1, 0)
+    } else {
+        end
+    };
+
+    Some(ExportEdits {
+        remove_export: Range::new(start, position(export.decl.span().lo)),
+        delete_declaration: Range::new(start, end),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use lsp_types::{Position, Range};
+
+    use crate::lsp::{export_edits, ExportEdits};
+
+    #[test]
+    fn export_edits_of_declarations() {
+        let path = PathBuf::from("module.ts");
+        let source = "import { a } from './a';\n\nexport const unused = a;\nexport class Service {\n  run() {}\n}\nexport let x = 1, y = 2;\n";
+
+        assert_eq!(
+            export_edits(&path, source, "unused"),
+            Some(ExportEdits {
+                remove_export: Range::new(Position::new(2, 0), Position::new(2, 7)),
+                delete_declaration: Range::new(Position::new(2, 0), Position::new(3, 0)),
+            })
+        );
+        assert_eq!(
+            export_edits(&path, source, "Service"),
+            Some(ExportEdits {
+                remove_export: Range::new(Position::new(3, 0), Position::new(3, 7)),
+                delete_declaration: Range::new(Position::new(3, 0), Position::new(6, 0)),
+            })
+        );
+        assert_eq!(export_edits(&path, source, "x"), None);
+        assert_eq!(export_edits(&path, source, "missing"), None);
+    }
+
+    #[test]
+    fn utf16_columns() {
+        let path = PathBuf::from("module.ts");
+        let source = "/* 🦀 */ export const a = 1;\nexport const b = '🦀'; // é\n";
+
+        assert_eq!(
+            export_edits(&path, source, "a"),
+            Some(ExportEdits {
+                remove_export: Range::new(Position::new(0, 9), Position::new(0, 16)),
+                delete_declaration: Range::new(Position::new(0, 9), Position::new(1, 0)),
+            })
+        );
+        assert_eq!(
+            export_edits(&path, source, "b"),
+            Some(ExportEdits {
+                remove_export: Range::new(Position::new(1, 0), Position::new(1, 7)),
+                delete_declaration: Range::new(Position::new(1, 0), Position::new(1, 22)),
+            })
+        );
+    }
+}
@@ -1,41 +1,230 @@
-mod analyze_file;
-mod analyze_package;
-mod analyze_plan;
-mod analyze_symbols_usage;
-mod analyzed_module;
-mod find_unused_exports;
-mod module_symbols;
-mod package_json;
-mod resolve_import_path;
-mod source_map;
-mod tsconfig;
-
-use std::collections::HashSet;
+mod lsp;
+mod report;
+mod watch;
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::env::current_dir;
-use std::{collections::HashMap, path::PathBuf};
+use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
-use clap::Parser;
-use find_unused_exports::UnusedExport;
+use clap::{Parser, Subcommand, ValueEnum};
+use rayon::ThreadPoolBuilder;
 use regex::Regex;
+use ts_unused_symbols::{
+    analyze_packages, build_graph, explain_export, find_changed_unused_exports, find_dead_barrels,
+    find_dependents, find_duplicate_exports, find_module_cycles, find_package_cycles,
+    find_redundant_reexports, find_type_only_exports, find_type_only_imports,
+    find_unlisted_dependencies, find_unused_class_members, find_unused_dependencies,
+    find_unused_exports, find_unused_locals, find_unused_members, prepare_analyze_plan,
+    AnalysisCache, AnalyzeOptions, AnalyzedPackage, DependencyOptions, DuplicateExport,
+    FindingKind, GitChanges, GraphOptions, ImportKind, RedundantReexport, StaleSuppression,
+    Suppressions, Symbol, TypeOnlyExport, TypeOnlyImport, UnlistedDependency, UnusedExport,
+    UnusedExportKind, UnusedLocal, UnusedMember,
+};
 
-use crate::analyze_package::{analyze_package, AnalyzeOptions, AnalyzedPackage};
-use crate::analyze_plan::prepare_analyze_plan;
-use crate::find_unused_exports::{find_unused_exports, Symbol};
+use crate::report::{
+    print_dead_barrels, print_dependents, print_duplicate_exports, print_explanation,
+    print_import_cycles, print_imports_by_kind, print_redundant_reexports,
+    print_stale_suppressions, print_type_only_exports, print_type_only_imports,
+    print_unlisted_dependencies, print_unresolved_paths, print_unused_dependencies,
+    print_unused_locals, print_unused_members, symbol_name,
+};
+use crate::watch::watch;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     path: Option<String>,
 
-    #[arg(short, long, help = "Include into analysis but ignore unused symbols")]
+    #[arg(
+        short,
+        long,
+        global = true,
+        help = "Include into analysis but ignore unused symbols"
+    )]
     ignore_patterns: Vec<String>,
 
-    #[arg(short, long, help = "Completely exclude from the analysis")]
+    #[arg(
+        short,
+        long,
+        global = true,
+        help = "Completely exclude from the analysis"
+    )]
     exclude_patterns: Vec<String>,
 
-    #[arg(short, long, help = "Shows unresolved paths")]
+    #[arg(short, long, help = "Shows imports by kind and unresolved paths")]
     verbose: bool,
+
+    #[arg(long, help = "Report dependencies in package.json never imported")]
+    unused_dependencies: bool,
+
+    #[arg(long, help = "Never report dependencies providing an executable")]
+    allow_binaries: bool,
+
+    #[arg(long, help = "Never report @types/* dependencies")]
+    allow_types_packages: bool,
+
+    #[arg(long, help = "Additional config files referencing dependencies")]
+    config_files: Vec<String>,
+
+    #[arg(long, help = "Never report dependencies matching the pattern")]
+    ignore_dependencies: Vec<String>,
+
+    #[arg(
+        long,
+        help = "Report imported packages missing in package.json dependencies"
+    )]
+    unlisted_dependencies: bool,
+
+    #[arg(
+        long,
+        help = "Report top-level declarations neither exported nor referenced in the module"
+    )]
+    unused_locals: bool,
+
+    #[arg(long, help = "Report members of exported enums never accessed")]
+    unused_members: bool,
+
+    #[arg(
+        long,
+        help = "Also report properties of exported `as const` objects, implies --unused-members"
+    )]
+    const_object_members: bool,
+
+    #[arg(
+        long,
+        help = "Report public members of exported classes never accessed as a property"
+    )]
+    unused_class_members: bool,
+
+    #[arg(long, help = "Report value exports only ever imported as types")]
+    type_only_exports: bool,
+
+    #[arg(
+        long,
+        help = "Report imports used only as types which should be `import type`"
+    )]
+    type_only_imports: bool,
+
+    #[arg(
+        long,
+        help = "Report import cycles between modules and between packages"
+    )]
+    import_cycles: bool,
+
+    #[arg(long, help = "Leave type-only imports out of the import cycles")]
+    exclude_type_imports: bool,
+
+    #[arg(
+        long,
+        help = "Report names re-exported from several modules and bindings exported both by name and as default"
+    )]
+    duplicate_exports: bool,
+
+    #[arg(
+        long,
+        help = "Report re-exports forwarding nothing imported and barrel files nobody imports"
+    )]
+    redundant_reexports: bool,
+
+    #[arg(
+        short,
+        long,
+        global = true,
+        help = "Number of threads, defaults to the number of CPUs"
+    )]
+    jobs: Option<usize>,
+
+    #[arg(
+        long,
+        global = true,
+        help = "Reuse the symbols of files unchanged since the previous run"
+    )]
+    cache: bool,
+
+    #[arg(
+        long,
+        global = true,
+        help = "Location of the analysis cache, implies --cache, defaults to node_modules/.cache/ts-unused-symbols"
+    )]
+    cache_dir: Option<String>,
+
+    #[arg(
+        long,
+        global = true,
+        value_delimiter = ',',
+        default_value = "public,api",
+        help = "JSDoc tags marking exports as used"
+    )]
+    suppression_tags: Vec<String>,
+
+    #[arg(
+        long,
+        help = "Report suppression comments which no longer suppress any finding, judged only when all the checks they target ran"
+    )]
+    stale_suppressions: bool,
+
+    #[arg(
+        long,
+        help = "Keep running, re-analyze changed files and print the findings which changed"
+    )]
+    watch: bool,
+
+    #[arg(
+        long,
+        value_name = "REF",
+        help = "Report only unused exports declared in files changed since the git ref, or no longer imported by them"
+    )]
+    changed_since: Option<String>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Run a language server over stdio publishing unused exports as diagnostics
+    Lsp,
+
+    /// Print the importers keeping an export alive, `default` for the default export
+    Explain { file: PathBuf, symbol: String },
+
+    /// List the modules importing the file directly or through other modules
+    Dependents { file: PathBuf },
+
+    /// Print the import graph of the modules or the packages
+    Graph {
+        #[arg(long, value_enum, default_value_t = GraphFormat::Dot)]
+        format: GraphFormat,
+
+        #[arg(long, help = "One node per package instead of per module")]
+        collapse_packages: bool,
+
+        #[arg(long = "package", help = "Keep only the modules of the package")]
+        packages: Vec<String>,
+
+        #[arg(long = "pattern", help = "Keep only the modules matching the pattern")]
+        path_patterns: Vec<String>,
+
+        #[arg(
+            long,
+            help = "Keep only the modules at most this many imports away from the roots"
+        )]
+        depth: Option<usize>,
+
+        #[arg(
+            long = "root",
+            help = "Roots of --depth, defaults to the package entry points"
+        )]
+        roots: Vec<PathBuf>,
+    },
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum GraphFormat {
+    Dot,
+    Mermaid,
+    Json,
 }
 
 fn main() -> Result<()> {
@@ -52,42 +241,455 @@ fn main() -> Result<()> {
     let mut ignore_patterns = vec!["node_modules".to_string()];
     ignore_patterns.extend(args.ignore_patterns);
 
-    let options = AnalyzeOptions::new(
-        parse_regex_item(ignore_patterns.into_iter())?,
-        parse_regex_item(exclude_patterns.into_iter())?,
-    );
+    let mut options = AnalyzeOptions::builder()
+        .ignore_patterns(parse_regex_item(ignore_patterns.into_iter())?)
+        .exclude_patterns(parse_regex_item(exclude_patterns.into_iter())?)
+        .suppression_tags(args.suppression_tags);
+
+    if args.cache || args.cache_dir.is_some() {
+        options = options.cache(AnalysisCache::new(
+            args.cache_dir
+                .map(PathBuf::from)
+                .unwrap_or_else(|| path.join("node_modules/.cache/ts-unused-symbols")),
+        ));
+    }
+
+    let options = options.build();
+
+    if let Some(jobs) = args.jobs {
+        ThreadPoolBuilder::new()
+            .num_threads(jobs)
+            .build_global()
+            .context("Couldnt configure the thread pool")?;
+    }
+
+    if let Some(Command::Lsp) = args.command {
+        return lsp::run(options, path);
+    }
 
     let analyze_plan = prepare_analyze_plan(&path)?;
 
-    let analyzed_packages = analyze_plan
-        .packages
-        .iter()
-        .map(|package| {
-            analyze_package(
-                &package.path,
-                &package.tsconfig,
-                &options,
-                &analyze_plan.packages,
-            )
-        })
-        .collect::<Result<Vec<AnalyzedPackage>>>()?;
+    for skipped_path in &analyze_plan.skipped_paths {
+        eprintln!("Package not found in {skipped_path:?}, skipping...");
+    }
+
+    let analyzed_packages = analyze_packages(&analyze_plan.packages, &options)?;
+
+    for analyzed_package in &analyzed_packages {
+        for (path, message) in &analyzed_package.unparsed_files {
+            eprintln!("Failed to parse {path:?}: {message}, skipping...");
+        }
+    }
+
+    if let Some(Command::Explain { file, symbol }) = &args.command {
+        let file = file
+            .canonicalize()
+            .with_context(|| format!("Couldnt find {file:?}"))?;
+        let symbol = match symbol.as_str() {
+            "default" => Symbol::Default,
+            s => Symbol::Symbol(s.to_owned()),
+        };
+
+        let explanation =
+            explain_export(&analyzed_packages, &file, &symbol).with_context(|| {
+                format!(
+                    "{} doesnt export {}",
+                    file.to_str().unwrap(),
+                    symbol_name(&symbol)
+                )
+            })?;
+        print_explanation(&explanation);
+
+        return Ok(());
+    }
+
+    if let Some(Command::Dependents { file }) = &args.command {
+        let file = file
+            .canonicalize()
+            .with_context(|| format!("Couldnt find {file:?}"))?;
+
+        print_dependents(&file, &find_dependents(&analyzed_packages, &file));
 
-    let unused_exports = find_unused_exports(&analyzed_packages);
-    let final_unused_exports = filter_ignored(&unused_exports, &options.ignore_patterns);
-    let number_of_ignored = unused_exports.len() - final_unused_exports.len();
+        return Ok(());
+    }
+
+    let mut suppressions = Suppressions::new(&analyzed_packages, options.suppression_tags());
+
+    suppressions.check(FindingKind::UnusedExports);
+    let mut unused_exports = find_unused_exports(&analyzed_packages);
+    unused_exports.retain(|e| !suppressions.suppresses_export(e));
+
+    let reported_unused_exports = match &args.changed_since {
+        Some(since) => find_changed_unused_exports(
+            &unused_exports,
+            &analyzed_packages,
+            &analyze_plan.packages,
+            &options,
+            &GitChanges::new(&path, since)?,
+        )?,
+        None => unused_exports.clone(),
+    };
+
+    let number_of_reported = reported_unused_exports.len();
+    let final_unused_exports = filter_ignored(reported_unused_exports, options.ignore_patterns());
+    let number_of_ignored = number_of_reported - final_unused_exports.len();
+
+    if let Some(Command::Graph {
+        format,
+        collapse_packages,
+        packages,
+        path_patterns,
+        depth,
+        roots,
+    }) = args.command
+    {
+        let graph_options = GraphOptions {
+            collapse_packages,
+            packages,
+            path_patterns: parse_regex_item(path_patterns.into_iter())?,
+            depth,
+            roots: roots
+                .iter()
+                .map(|r| {
+                    r.canonicalize()
+                        .with_context(|| format!("Couldnt find {r:?}"))
+                })
+                .collect::<Result<_>>()?,
+        };
+
+        let graph = build_graph(
+            &analyzed_packages,
+            &analyze_plan.packages,
+            &final_unused_exports,
+            &path,
+            &graph_options,
+        );
+
+        match format {
+            GraphFormat::Dot => print!("{}", graph.to_dot()),
+            GraphFormat::Mermaid => print!("{}", graph.to_mermaid()),
+            GraphFormat::Json => println!("{}", serde_json::to_string_pretty(&graph)?),
+        }
+
+        return Ok(());
+    }
     let number_of_files = analyzed_packages
         .iter()
         .map(|p| p.modules.len())
         .sum::<usize>();
 
+    let (final_unused_exports, used_locally_exports): (Vec<_>, Vec<_>) = final_unused_exports
+        .into_iter()
+        .partition(|e| e.kind == UnusedExportKind::Unused);
+
     print_unsed_exports(&final_unused_exports);
 
+    if !used_locally_exports.is_empty() {
+        println!();
+        println!("Used only in the declaring module");
+        print_unsed_exports(&used_locally_exports);
+    }
+
+    let unused_locals = if args.unused_locals {
+        suppressions.check(FindingKind::UnusedLocals);
+        let unused_locals = find_unused_locals(&analyzed_packages, &unused_exports)
+            .into_iter()
+            .filter(|l| {
+                !suppressions.suppresses(FindingKind::UnusedLocals, &l.filename, l.location.line)
+            })
+            .collect();
+        let unused_locals = filter_ignored(unused_locals, options.ignore_patterns());
+
+        println!();
+        print_unused_locals(&unused_locals);
+
+        Some(unused_locals)
+    } else {
+        None
+    };
+
+    let unused_members = if args.unused_members || args.const_object_members {
+        suppressions.check(FindingKind::UnusedMembers);
+        let unused_members = find_unused_members(&analyzed_packages, args.const_object_members)
+            .into_iter()
+            .filter(|m| {
+                !suppressions.suppresses(FindingKind::UnusedMembers, &m.filename, m.location.line)
+            })
+            .collect();
+        let unused_members = filter_ignored(unused_members, options.ignore_patterns());
+
+        println!();
+        print_unused_members(&unused_members);
+
+        Some(unused_members)
+    } else {
+        None
+    };
+
+    let unused_class_members = if args.unused_class_members {
+        suppressions.check(FindingKind::UnusedClassMembers);
+        let unused_class_members = find_unused_class_members(&analyzed_packages)
+            .into_iter()
+            .filter(|m| {
+                !suppressions.suppresses(
+                    FindingKind::UnusedClassMembers,
+                    &m.filename,
+                    m.location.line,
+                )
+            })
+            .collect();
+        let unused_class_members = filter_ignored(unused_class_members, options.ignore_patterns());
+
+        println!();
+        print_unused_members(&unused_class_members);
+
+        Some(unused_class_members)
+    } else {
+        None
+    };
+
+    let type_only_exports = if args.type_only_exports {
+        suppressions.check(FindingKind::TypeOnlyExports);
+        let type_only_exports = find_type_only_exports(&analyzed_packages)
+            .into_iter()
+            .filter(|e| {
+                !suppressions.suppresses_symbol(
+                    FindingKind::TypeOnlyExports,
+                    &e.filename,
+                    &e.symbol,
+                )
+            })
+            .collect();
+        let type_only_exports = filter_ignored(type_only_exports, options.ignore_patterns());
+
+        println!();
+        print_type_only_exports(&type_only_exports);
+
+        Some(type_only_exports)
+    } else {
+        None
+    };
+
+    let type_only_imports = if args.type_only_imports {
+        suppressions.check(FindingKind::TypeOnlyImports);
+        let type_only_imports = find_type_only_imports(&analyzed_packages)
+            .into_iter()
+            .filter(|i| {
+                !suppressions.suppresses(FindingKind::TypeOnlyImports, &i.filename, i.location.line)
+            })
+            .collect();
+        let type_only_imports = filter_ignored(type_only_imports, options.ignore_patterns());
+
+        println!();
+        print_type_only_imports(&type_only_imports);
+
+        Some(type_only_imports)
+    } else {
+        None
+    };
+
+    let duplicate_exports = if args.duplicate_exports {
+        suppressions.check(FindingKind::DuplicateExports);
+        let duplicate_exports = find_duplicate_exports(&analyzed_packages)
+            .into_iter()
+            .filter(|e| !suppressions.suppresses_duplicate_export(e))
+            .collect();
+        let duplicate_exports = filter_ignored(duplicate_exports, options.ignore_patterns());
+
+        println!();
+        print_duplicate_exports(&duplicate_exports);
+
+        Some(duplicate_exports)
+    } else {
+        None
+    };
+
+    let redundant_reexports = if args.redundant_reexports {
+        // barrels exposed by the package are its public API
+        let entry_points = analyzed_packages
+            .iter()
+            .flat_map(|p| p.entry_points.iter())
+            .collect::<HashSet<_>>();
+
+        suppressions.check(FindingKind::RedundantReexports);
+        let redundant_reexports = find_redundant_reexports(&analyzed_packages)
+            .into_iter()
+            .filter(|r| !entry_points.contains(&r.filename))
+            .filter(|r| {
+                !suppressions.suppresses(
+                    FindingKind::RedundantReexports,
+                    &r.filename,
+                    r.location.line,
+                )
+            })
+            .collect();
+        let redundant_reexports = filter_ignored(redundant_reexports, options.ignore_patterns());
+        let dead_barrels = find_dead_barrels(&analyzed_packages)
+            .into_iter()
+            .filter(|b| !entry_points.contains(b))
+            .filter(|b| !suppressions.suppresses_file(FindingKind::RedundantReexports, b))
+            .collect();
+        let dead_barrels = filter_ignored(dead_barrels, options.ignore_patterns());
+
+        println!();
+        print_redundant_reexports(&redundant_reexports);
+        print_dead_barrels(&dead_barrels);
+
+        Some((redundant_reexports, dead_barrels))
+    } else {
+        None
+    };
+
+    let import_cycles = if args.import_cycles {
+        suppressions.check(FindingKind::ImportCycles);
+        let module_cycles = find_module_cycles(&analyzed_packages, !args.exclude_type_imports)
+            .into_iter()
+            .filter(|c| !suppressions.suppresses_cycle(c))
+            .collect::<Vec<_>>();
+        let package_cycles = find_package_cycles(&analyzed_packages, !args.exclude_type_imports);
+
+        println!();
+        print_import_cycles(&module_cycles, "modules");
+
+        if !package_cycles.is_empty() {
+            println!();
+            println!("Cycles between packages");
+            print_import_cycles(&package_cycles, "packages");
+        }
+
+        Some((module_cycles, package_cycles))
+    } else {
+        None
+    };
+
+    let unused_dependencies = if args.unused_dependencies {
+        let dependency_options = DependencyOptions::new(
+            args.allow_binaries,
+            args.allow_types_packages,
+            args.config_files,
+            parse_regex_item(args.ignore_dependencies.into_iter())?,
+        );
+        let unused_dependencies =
+            find_unused_dependencies(&analyzed_packages, &dependency_options)?;
+
+        println!();
+        print_unused_dependencies(&unused_dependencies);
+
+        Some(unused_dependencies)
+    } else {
+        None
+    };
+
+    let unlisted_dependencies = if args.unlisted_dependencies {
+        suppressions.check(FindingKind::UnlistedDependencies);
+        let unlisted_dependencies = find_unlisted_dependencies(&analyzed_packages)?
+            .into_iter()
+            .filter(|d| {
+                !suppressions.suppresses(
+                    FindingKind::UnlistedDependencies,
+                    &d.filename,
+                    d.location.line,
+                )
+            })
+            .collect();
+        let unlisted_dependencies =
+            filter_ignored(unlisted_dependencies, options.ignore_patterns());
+
+        println!();
+        print_unlisted_dependencies(&unlisted_dependencies);
+
+        Some(unlisted_dependencies)
+    } else {
+        None
+    };
+
+    let stale_suppressions = if args.stale_suppressions {
+        let stale_suppressions = filter_ignored(suppressions.stale(), options.ignore_patterns());
+
+        println!();
+        print_stale_suppressions(&stale_suppressions);
+
+        Some(stale_suppressions)
+    } else {
+        None
+    };
+
     println!();
     println!(" - {} unused exports", final_unused_exports.len());
+    println!(
+        " - {} exports used only in the declaring module",
+        used_locally_exports.len()
+    );
     println!(" - {number_of_ignored} unused exports ignored in the report",);
     println!(" - {number_of_files} files analyzed");
 
+    if let Some(unused_locals) = unused_locals {
+        println!(" - {} unused local declarations", unused_locals.len());
+    }
+
+    if let Some(unused_members) = unused_members {
+        println!(" - {} unused members", unused_members.len());
+    }
+
+    if let Some(unused_class_members) = unused_class_members {
+        println!(" - {} unused class members", unused_class_members.len());
+    }
+
+    if let Some(type_only_exports) = type_only_exports {
+        println!(
+            " - {} exports only imported as types",
+            type_only_exports.len()
+        );
+    }
+
+    if let Some(type_only_imports) = type_only_imports {
+        println!(
+            " - {} imports which should be type-only",
+            type_only_imports.len()
+        );
+    }
+
+    if let Some(duplicate_exports) = duplicate_exports {
+        println!(" - {} duplicate exports", duplicate_exports.len());
+    }
+
+    if let Some((redundant_reexports, dead_barrels)) = redundant_reexports {
+        println!(" - {} redundant re-exports", redundant_reexports.len());
+        println!(" - {} barrel files nobody imports", dead_barrels.len());
+    }
+
+    if let Some((module_cycles, package_cycles)) = import_cycles {
+        println!(" - {} import cycles between modules", module_cycles.len());
+        println!(" - {} import cycles between packages", package_cycles.len());
+    }
+
+    if let Some(unused_dependencies) = unused_dependencies {
+        println!(" - {} unused dependencies", unused_dependencies.len());
+    }
+
+    if let Some(unlisted_dependencies) = unlisted_dependencies {
+        println!(" - {} unlisted dependencies", unlisted_dependencies.len());
+    }
+
+    if let Some(stale_suppressions) = stale_suppressions {
+        println!(" - {} stale suppressions", stale_suppressions.len());
+    }
+
     if args.verbose {
+        let mut imports_by_kind: BTreeMap<ImportKind, HashSet<String>> = BTreeMap::new();
+
+        for analyzed_package in &analyzed_packages {
+            for (kind, imports) in &analyzed_package.imports_by_kind {
+                imports_by_kind
+                    .entry(*kind)
+                    .or_default()
+                    .extend(imports.clone());
+            }
+        }
+
+        println!();
+        print_imports_by_kind(&imports_by_kind);
+
         let unresolved_paths = analyzed_packages
             .iter()
             .flat_map(|p| p.unresolved_paths.clone())
@@ -97,26 +699,172 @@ fn main() -> Result<()> {
         print_unresolved_paths(&unresolved_paths);
     }
 
+    if args.watch {
+        let findings = |analyzed_packages: &[AnalyzedPackage]| {
+            watched_findings(
+                analyzed_packages,
+                &options,
+                args.unused_locals,
+                args.unused_members || args.const_object_members,
+                args.const_object_members,
+                args.unused_class_members,
+            )
+        };
+
+        watch(
+            &path,
+            &analyze_plan.packages,
+            &options,
+            analyzed_packages,
+            findings,
+        )?;
+    }
+
     Ok(())
 }
 
+/// Findings tracked in watch mode, one line each.
+fn watched_findings(
+    analyzed_packages: &[AnalyzedPackage],
+    options: &AnalyzeOptions,
+    unused_locals: bool,
+    unused_members: bool,
+    const_object_members: bool,
+    unused_class_members: bool,
+) -> BTreeSet<String> {
+    let mut suppressions = Suppressions::new(analyzed_packages, options.suppression_tags());
+
+    suppressions.check(FindingKind::UnusedExports);
+    let mut unused_exports = find_unused_exports(analyzed_packages);
+    unused_exports.retain(|e| !suppressions.suppresses_export(e));
+    let mut findings = BTreeSet::new();
+
+    for export in filter_ignored(unused_exports.clone(), options.ignore_patterns()) {
+        let symbol = match &export.symbol {
+            Symbol::Symbol(s) => s.to_owned(),
+            Symbol::Default => "DEFAULT".into(),
+        };
+        let kind = match export.kind {
+            UnusedExportKind::Unused => "unused export",
+            UnusedExportKind::UsedLocally => "export used only in the declaring module",
+        };
+
+        findings.insert(format!(
+            "{}: {symbol} ({kind})",
+            export.filename.to_str().unwrap()
+        ));
+    }
+
+    if unused_locals {
+        suppressions.check(FindingKind::UnusedLocals);
+
+        let unused_locals = find_unused_locals(analyzed_packages, &unused_exports);
+
+        for local in filter_ignored(unused_locals, options.ignore_patterns()) {
+            if !suppressions.suppresses(
+                FindingKind::UnusedLocals,
+                &local.filename,
+                local.location.line,
+            ) {
+                findings.insert(format!(
+                    "{}:{}:{}: {} (unused local declaration)",
+                    local.filename.to_str().unwrap(),
+                    local.location.line,
+                    local.location.column,
+                    local.name
+                ));
+            }
+        }
+    }
+
+    let mut members = vec![];
+    if unused_members {
+        suppressions.check(FindingKind::UnusedMembers);
+        let unused_members = find_unused_members(analyzed_packages, const_object_members);
+        members.extend(
+            filter_ignored(unused_members, options.ignore_patterns())
+                .into_iter()
+                .map(|m| (FindingKind::UnusedMembers, m)),
+        );
+    }
+    if unused_class_members {
+        suppressions.check(FindingKind::UnusedClassMembers);
+        let unused_class_members = find_unused_class_members(analyzed_packages);
+        members.extend(
+            filter_ignored(unused_class_members, options.ignore_patterns())
+                .into_iter()
+                .map(|m| (FindingKind::UnusedClassMembers, m)),
+        );
+    }
+
+    for (_, member) in members
+        .iter()
+        .filter(|(kind, m)| !suppressions.suppresses(*kind, &m.filename, m.location.line))
+    {
+        findings.insert(format!(
+            "{}:{}:{}: {}.{} (unused member)",
+            member.filename.to_str().unwrap(),
+            member.location.line,
+            member.location.column,
+            member.symbol,
+            member.member
+        ));
+    }
+
+    findings
+}
+
 fn parse_regex_item<I: Iterator<Item = String>>(i: I) -> Result<Vec<Regex>> {
     i.map(|p| Regex::new(&p).with_context(|| format!("Failed to parse regex {p}")))
         .collect()
 }
 
-fn filter_ignored(unused_exports: &[UnusedExport], ignore_patterns: &[Regex]) -> Vec<UnusedExport> {
-    unused_exports
-        .iter()
-        .filter(|e| {
-            !ignore_patterns
-                .iter()
-                .any(|r| r.is_match(e.filename.to_str().unwrap()))
-        })
-        .cloned()
+/// Finding reported in a file, matched against the ignore patterns.
+trait Finding {
+    fn filename(&self) -> &Path;
+}
+
+macro_rules! impl_finding {
+    ($($finding:ty),*) => {
+        $(impl Finding for $finding {
+            fn filename(&self) -> &Path {
+                &self.filename
+            }
+        })*
+    };
+}
+
+impl_finding!(
+    UnusedExport,
+    UnusedLocal,
+    UnusedMember,
+    TypeOnlyExport,
+    TypeOnlyImport,
+    DuplicateExport,
+    RedundantReexport,
+    UnlistedDependency,
+    StaleSuppression
+);
+
+// dead barrels are reported as the file itself
+impl Finding for PathBuf {
+    fn filename(&self) -> &Path {
+        self
+    }
+}
+
+fn filter_ignored<T: Finding>(findings: Vec<T>, ignore_patterns: &[Regex]) -> Vec<T> {
+    findings
+        .into_iter()
+        .filter(|f| !is_ignored(f.filename(), ignore_patterns))
         .collect()
 }
 
+fn is_ignored(filename: &Path, ignore_patterns: &[Regex]) -> bool {
+    let filename = filename.to_string_lossy();
+    ignore_patterns.iter().any(|r| r.is_match(&filename))
+}
+
 fn print_unsed_exports(unused_exports: &[UnusedExport]) {
     let unused_exports_stdout = group_by_path(unused_exports)
         .iter()
@@ -143,23 +891,15 @@ fn print_unsed_exports(unused_exports: &[UnusedExport]) {
     println!("{unused_exports_stdout}");
 }
 
-fn group_by_path(unused_exports: &[UnusedExport]) -> HashMap<PathBuf, Vec<UnusedExport>> {
-    let mut result: HashMap<PathBuf, Vec<UnusedExport>> = HashMap::new();
+fn group_by_path(unused_exports: &[UnusedExport]) -> BTreeMap<PathBuf, Vec<UnusedExport>> {
+    let mut result: BTreeMap<PathBuf, Vec<UnusedExport>> = BTreeMap::new();
 
     for unused_export in unused_exports {
         result
             .entry(unused_export.filename.to_owned())
-            .or_insert_with(Vec::new)
+            .or_default()
             .push(unused_export.to_owned());
     }
 
     result
 }
-
-fn print_unresolved_paths(unresolved_paths: &HashSet<String>) {
-    println!("Unresolved paths");
-
-    for path in unresolved_paths {
-        println!(" - {path:?}");
-    }
-}
@@ -1,44 +1,219 @@
-use std::{collections::HashSet, hash::Hash};
+use std::{
+    collections::{BTreeSet, HashSet},
+    hash::Hash,
+};
 
+use serde::{Deserialize, Serialize};
 use swc_ecma_ast::Ident;
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(bound(deserialize = "P: Deserialize<'de> + Eq + Hash"))]
 pub struct ModuleSymbols<P> {
     pub usages: HashSet<Usage>,
     pub exports: HashSet<Export<P>>,
     pub imports: HashSet<ImportedSymbol<P>>,
+    pub references: HashSet<ModuleReference>,
+    pub declarations: HashSet<Declaration>,
+    pub reexports: HashSet<Reexport<P>>,
+    pub suppressions: HashSet<Suppression>,
+
+    // top-level symbols referenced outside of any declaration
+    pub top_level_references: HashSet<String>,
+}
+
+/// Line and column are both 1-based.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default, Serialize, Deserialize,
+)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Module specifier as written in an import or a re-export statement.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ModuleReference {
+    pub specifier: String,
+    pub location: Location,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// Top-level declaration of a module.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Declaration {
+    pub name: String,
+    pub location: Location,
+
+    // names the declaration is exported under
+    pub exported_as: BTreeSet<String>,
+
+    // top-level symbols referenced from within the declaration
+    pub references: BTreeSet<String>,
+
+    // members of enums and `as const` object literals
+    pub members: Vec<Member>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Member {
+    pub name: String,
+    pub location: Location,
+    pub kind: MemberKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MemberKind {
+    EnumMember,
+    ConstObjectProperty,
+    ClassMember,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Usage {
-    //Symbol(String),
+    // reference to a top-level binding of the module
+    Symbol(String),
 
     // (symbol, namespace alias)
     Namespace(String, String),
+
+    // reference to a top-level binding in a type position
+    Type(String),
+
+    // <symbol>.<member> access on a top-level binding
+    Member(String, String),
+
+    // (symbol, member, namespace alias)
+    NamespaceMember(String, String, String),
+
+    // <expr>.<property> access on anything else (`this`, locals, call results)
+    Property(String),
+
+    // this[<expr>] with a key not known statically
+    DynamicProperty,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Export<P> {
-    Symbol(String),
+    Symbol(String, SymbolKind),
     AllFrom(P),
     Default,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct Reexport<P> {
-    pub from: P,
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SymbolKind {
+    // classes, enums, functions, variables, ...
+    Value,
+
+    // interfaces, type aliases and `export type { ... }`
+    Type,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct ImportedSymbol<P> {
     pub symbols: Vec<Import>,
     pub from: P,
+
+    // `import type { ... }` or `import { type ... }`
+    pub type_only: bool,
+    pub location: Location,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// `export * from` or `export { ... } from` statement.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Reexport<P> {
+    pub from: P,
+    pub kind: ReexportKind,
+    pub location: Location,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ReexportKind {
+    // export * from <from>;
+    All,
+
+    // export { <name in from> as <exported name> } from <from>;
+    Named(Vec<(String, String)>),
+}
+
+/// Comment suppressing the findings on some lines of the module.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Suppression {
+    pub kind: SuppressionKind,
+    pub location: Location,
+
+    // suppressed lines, inclusive
+    pub first_line: usize,
+    pub last_line: usize,
+
+    // kinds of findings the comment names, all of them when empty
+    pub targets: BTreeSet<FindingKind>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum SuppressionKind {
+    // /* ts-unused-symbols-disable */
+    File,
+
+    // // ts-unused-symbols-ignore-next-line
+    NextLine,
+
+    // JSDoc `@<tag>` of a top-level statement, suppresses only its exports
+    Tag(String),
+}
+
+/// Kinds of findings suppression comments apply to, named like the flags
+/// enabling their check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum FindingKind {
+    UnusedExports,
+    UnusedLocals,
+    UnusedMembers,
+    UnusedClassMembers,
+    TypeOnlyExports,
+    TypeOnlyImports,
+    DuplicateExports,
+    RedundantReexports,
+    ImportCycles,
+    UnlistedDependencies,
+}
+
+impl FindingKind {
+    pub const ALL: [FindingKind; 10] = [
+        FindingKind::UnusedExports,
+        FindingKind::UnusedLocals,
+        FindingKind::UnusedMembers,
+        FindingKind::UnusedClassMembers,
+        FindingKind::TypeOnlyExports,
+        FindingKind::TypeOnlyImports,
+        FindingKind::DuplicateExports,
+        FindingKind::RedundantReexports,
+        FindingKind::ImportCycles,
+        FindingKind::UnlistedDependencies,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            FindingKind::UnusedExports => "unused-exports",
+            FindingKind::UnusedLocals => "unused-locals",
+            FindingKind::UnusedMembers => "unused-members",
+            FindingKind::UnusedClassMembers => "unused-class-members",
+            FindingKind::TypeOnlyExports => "type-only-exports",
+            FindingKind::TypeOnlyImports => "type-only-imports",
+            FindingKind::DuplicateExports => "duplicate-exports",
+            FindingKind::RedundantReexports => "redundant-reexports",
+            FindingKind::ImportCycles => "import-cycles",
+            FindingKind::UnlistedDependencies => "unlisted-dependencies",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.name() == name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Import {
-    // import { <String> } from <from>;
-    Named(String),
+    // import { <String> as <String> } from <from>;
+    Named(String, String),
 
     // import <String> from <from>;
     Default(String),
@@ -62,13 +237,20 @@ impl ModuleSymbols<String> {
         }
     }
 
-    pub fn new_exported_symbol(symbol: Ident) -> Self {
-        Self::new_exported_symbol_str(symbol.sym.to_string())
+    pub fn new_exported_symbol(symbol: Ident, kind: SymbolKind) -> Self {
+        Self::new_exported_symbol_str(symbol.sym.to_string(), kind)
+    }
+
+    pub fn new_exported_symbol_str(symbol: String, kind: SymbolKind) -> Self {
+        Self {
+            exports: HashSet::from([Export::Symbol(symbol, kind)]),
+            ..Default::default()
+        }
     }
 
-    pub fn new_exported_symbol_str(symbol: String) -> Self {
+    pub fn new_reexport(reexport: Reexport<String>) -> Self {
         Self {
-            exports: HashSet::from([Export::Symbol(symbol)]),
+            reexports: HashSet::from([reexport]),
             ..Default::default()
         }
     }
@@ -90,10 +272,30 @@ impl ModuleSymbols<String> {
         let mut usages = self.usages;
         usages.extend(analyzed_module.usages);
 
+        let mut references = self.references;
+        references.extend(analyzed_module.references);
+
+        let mut declarations = self.declarations;
+        declarations.extend(analyzed_module.declarations);
+
+        let mut reexports = self.reexports;
+        reexports.extend(analyzed_module.reexports);
+
+        let mut suppressions = self.suppressions;
+        suppressions.extend(analyzed_module.suppressions);
+
+        let mut top_level_references = self.top_level_references;
+        top_level_references.extend(analyzed_module.top_level_references);
+
         Self {
             exports,
             imports,
             usages,
+            references,
+            declarations,
+            reexports,
+            suppressions,
+            top_level_references,
         }
     }
 }
@@ -0,0 +1,302 @@
+use serde_json::{Map, Value};
+
+/// Conditions TypeScript matches when resolving `exports`, the order of the
+/// keys in the package.json decides which one wins.
+pub const TYPESCRIPT_CONDITIONS: [&str; 4] = ["types", "import", "require", "node"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TargetResolution {
+    Resolved(String),
+
+    // explicitly excluded using `null`
+    Excluded,
+
+    // no subpath or condition matched
+    Unmatched,
+}
+
+impl TargetResolution {
+    fn into_option(self) -> Option<String> {
+        match self {
+            TargetResolution::Resolved(target) => Some(target),
+            TargetResolution::Excluded | TargetResolution::Unmatched => None,
+        }
+    }
+}
+
+/// Resolve `subpath` (either `.` or `./<something>`) against the `exports` field
+/// of a package.json following the Node.js `PACKAGE_EXPORTS_RESOLVE` algorithm.
+pub fn resolve_package_exports(
+    exports: &Value,
+    subpath: &str,
+    conditions: &[&str],
+) -> Option<String> {
+    match exports_subpath_map(exports) {
+        Some(map) => resolve_subpath_map(&map, subpath, conditions).into_option(),
+        None if subpath == "." => resolve_target(exports, None, conditions).into_option(),
+        None => None,
+    }
+}
+
+/// Resolve a `#`-prefixed `specifier` against the `imports` field of a package.json
+/// following the Node.js `PACKAGE_IMPORTS_RESOLVE` algorithm.
+pub fn resolve_package_imports(
+    imports: &Value,
+    specifier: &str,
+    conditions: &[&str],
+) -> Option<String> {
+    if specifier == "#" || specifier.starts_with("#/") {
+        return None;
+    }
+
+    match imports {
+        Value::Object(map) => resolve_subpath_map(map, specifier, conditions).into_option(),
+        _ => None,
+    }
+}
+
+/// List all the subpath keys of the `exports` field together with their
+/// targets. Wildcard keys keep the `*` in both the subpath and the target.
+pub fn package_exports_targets(exports: &Value, conditions: &[&str]) -> Vec<(String, String)> {
+    let map = match exports_subpath_map(exports) {
+        Some(map) => map,
+        None => Map::from_iter([(".".to_string(), exports.to_owned())]),
+    };
+
+    map.iter()
+        .filter_map(|(subpath, target)| {
+            resolve_target(target, None, conditions)
+                .into_option()
+                .map(|target| (subpath.to_owned(), target))
+        })
+        .collect()
+}
+
+/// `exports` can be a string, an array, a conditions object or an object with
+/// subpath keys. Only the last one is returned as is, the sugar forms are
+/// handled by the caller.
+fn exports_subpath_map(exports: &Value) -> Option<Map<String, Value>> {
+    match exports {
+        Value::Object(map) if map.keys().all(|k| k.starts_with('.')) && !map.is_empty() => {
+            Some(map.to_owned())
+        }
+        _ => None,
+    }
+}
+
+fn resolve_subpath_map(
+    map: &Map<String, Value>,
+    subpath: &str,
+    conditions: &[&str],
+) -> TargetResolution {
+    if !subpath.contains('*') {
+        if let Some(target) = map.get(subpath) {
+            return resolve_target(target, None, conditions);
+        }
+    }
+
+    let mut best_match: Option<(&str, &str)> = None;
+
+    for key in map.keys() {
+        let Some((base, trailer)) = key.split_once('*') else {
+            continue;
+        };
+
+        if trailer.contains('*') || !subpath.starts_with(base) || subpath == base {
+            continue;
+        }
+
+        if !trailer.is_empty() && (!subpath.ends_with(trailer) || subpath.len() < key.len()) {
+            continue;
+        }
+
+        if best_match.is_none_or(|(best_key, _)| pattern_key_compare(key, best_key)) {
+            let pattern_match = &subpath[base.len()..subpath.len() - trailer.len()];
+            best_match = Some((key, pattern_match));
+        }
+    }
+
+    match best_match {
+        Some((key, pattern_match)) => resolve_target(&map[key], Some(pattern_match), conditions),
+        None => TargetResolution::Unmatched,
+    }
+}
+
+/// `true` if `key` is more specific than `other` (`PATTERN_KEY_COMPARE`).
+fn pattern_key_compare(key: &str, other: &str) -> bool {
+    let base_length = key.find('*').unwrap_or(key.len());
+    let other_base_length = other.find('*').unwrap_or(other.len());
+
+    if base_length != other_base_length {
+        return base_length > other_base_length;
+    }
+
+    key.len() > other.len()
+}
+
+fn resolve_target(
+    target: &Value,
+    pattern_match: Option<&str>,
+    conditions: &[&str],
+) -> TargetResolution {
+    match target {
+        Value::String(target) => match pattern_match {
+            Some(pattern_match) => TargetResolution::Resolved(target.replace('*', pattern_match)),
+            None => TargetResolution::Resolved(target.to_owned()),
+        },
+        Value::Array(targets) => {
+            for target in targets {
+                match resolve_target(target, pattern_match, conditions) {
+                    TargetResolution::Unmatched => continue,
+                    resolution => return resolution,
+                }
+            }
+
+            TargetResolution::Unmatched
+        }
+        Value::Object(conditional_targets) => {
+            for (condition, target) in conditional_targets {
+                if condition != "default" && !conditions.contains(&condition.as_str()) {
+                    continue;
+                }
+
+                match resolve_target(target, pattern_match, conditions) {
+                    TargetResolution::Unmatched => continue,
+                    resolution => return resolution,
+                }
+            }
+
+            TargetResolution::Unmatched
+        }
+        Value::Null => TargetResolution::Excluded,
+        _ => TargetResolution::Unmatched,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::package_exports::{
+        package_exports_targets, resolve_package_exports, resolve_package_imports,
+        TYPESCRIPT_CONDITIONS,
+    };
+
+    #[test]
+    fn sugar_exports() {
+        let exports = json!("./dist/index.js");
+
+        assert_eq!(
+            resolve_package_exports(&exports, ".", &TYPESCRIPT_CONDITIONS),
+            Some("./dist/index.js".to_string())
+        );
+        assert_eq!(
+            resolve_package_exports(&exports, "./other", &TYPESCRIPT_CONDITIONS),
+            None
+        );
+    }
+
+    #[test]
+    fn nested_conditions() {
+        let exports = json!({
+            ".": {
+                "browser": "./dist/browser.js",
+                "node": {
+                    "types": "./dist/node.d.ts",
+                    "default": "./dist/node.js"
+                },
+                "default": "./dist/index.js"
+            }
+        });
+
+        assert_eq!(
+            resolve_package_exports(&exports, ".", &TYPESCRIPT_CONDITIONS),
+            Some("./dist/node.d.ts".to_string())
+        );
+        assert_eq!(
+            resolve_package_exports(&exports, ".", &["browser"]),
+            Some("./dist/browser.js".to_string())
+        );
+        assert_eq!(
+            resolve_package_exports(&exports, ".", &[]),
+            Some("./dist/index.js".to_string())
+        );
+    }
+
+    #[test]
+    fn subpath_patterns() {
+        let exports = json!({
+            "./*": "./dist/*.js",
+            "./utils/*": { "types": "./src/utils/*.ts" },
+            "./utils/*.css": "./styles/*.css",
+            "./utils/internal/*": null
+        });
+
+        assert_eq!(
+            resolve_package_exports(&exports, "./utils/format", &TYPESCRIPT_CONDITIONS),
+            Some("./src/utils/format.ts".to_string())
+        );
+        assert_eq!(
+            resolve_package_exports(&exports, "./utils/button.css", &TYPESCRIPT_CONDITIONS),
+            Some("./styles/button.css".to_string())
+        );
+        assert_eq!(
+            resolve_package_exports(&exports, "./utils/internal/secret", &TYPESCRIPT_CONDITIONS),
+            None
+        );
+        assert_eq!(
+            resolve_package_exports(&exports, "./other/file", &TYPESCRIPT_CONDITIONS),
+            Some("./dist/other/file.js".to_string())
+        );
+    }
+
+    #[test]
+    fn exports_targets() {
+        let exports = json!({
+            ".": { "types": "./dist/index.d.ts" },
+            "./utils/*": "./src/utils/*.ts",
+            "./internal": null
+        });
+
+        assert_eq!(
+            package_exports_targets(&exports, &TYPESCRIPT_CONDITIONS),
+            vec![
+                (".".to_string(), "./dist/index.d.ts".to_string()),
+                ("./utils/*".to_string(), "./src/utils/*.ts".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn subpath_imports() {
+        let imports = json!({
+            "#config": "./src/config.ts",
+            "#utils/*": {
+                "types": "./src/utils/*.ts",
+                "default": "./dist/utils/*.js"
+            },
+            "#dep": "lodash"
+        });
+
+        assert_eq!(
+            resolve_package_imports(&imports, "#config", &TYPESCRIPT_CONDITIONS),
+            Some("./src/config.ts".to_string())
+        );
+        assert_eq!(
+            resolve_package_imports(&imports, "#utils/log", &TYPESCRIPT_CONDITIONS),
+            Some("./src/utils/log.ts".to_string())
+        );
+        assert_eq!(
+            resolve_package_imports(&imports, "#utils/log", &[]),
+            Some("./dist/utils/log.js".to_string())
+        );
+        assert_eq!(
+            resolve_package_imports(&imports, "#dep", &TYPESCRIPT_CONDITIONS),
+            Some("lodash".to_string())
+        );
+        assert_eq!(
+            resolve_package_imports(&imports, "#other", &TYPESCRIPT_CONDITIONS),
+            None
+        );
+    }
+}
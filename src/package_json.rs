@@ -1,28 +1,104 @@
-use std::fs;
-use std::path::{Path, PathBuf};
+use std::collections::BTreeMap;
+use std::path::Path;
 
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+
+use crate::error::Result;
+use crate::json_file::try_load_json_file;
 
 #[derive(Debug, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct PackageJson {
+    // nested package.json files of build outputs only set the module type
+    #[serde(default)]
     pub name: String,
+    pub main: Option<String>,
     pub types: Option<String>,
+    pub exports: Option<Value>,
+    pub imports: Option<Value>,
+    #[serde(default, deserialize_with = "deserialize_workspaces")]
     pub workspaces: Option<Vec<String>>,
+    pub bin: Option<Value>,
+    #[serde(default)]
+    pub scripts: BTreeMap<String, String>,
+    #[serde(default)]
+    pub dependencies: BTreeMap<String, String>,
+    #[serde(default)]
+    pub dev_dependencies: BTreeMap<String, String>,
+    #[serde(default)]
+    pub peer_dependencies: BTreeMap<String, String>,
+    #[serde(default)]
+    pub optional_dependencies: BTreeMap<String, String>,
 }
 
-pub fn try_load_package_json(path: &Path) -> Option<PackageJson> {
-    let package_json_filename = "package.json";
+impl PackageJson {
+    pub fn declares_dependency(&self, name: &str) -> bool {
+        self.dependencies.contains_key(name)
+            || self.dev_dependencies.contains_key(name)
+            || self.peer_dependencies.contains_key(name)
+            || self.optional_dependencies.contains_key(name)
+    }
 
-    let mut package_json_path = path.to_owned();
-    package_json_path.push(PathBuf::from(package_json_filename));
+    /// Names of the executables the package provides.
+    pub fn binaries(&self) -> Vec<String> {
+        match &self.bin {
+            Some(Value::String(_)) => vec![self
+                .name
+                .rsplit('/')
+                .next()
+                .unwrap_or(&self.name)
+                .to_owned()],
+            Some(Value::Object(bin)) => bin.keys().cloned().collect(),
+            _ => vec![],
+        }
+    }
+}
 
-    if !package_json_path.exists() {
-        return None;
+/// `"workspaces": [...]` or the yarn `"workspaces": { "packages": [...] }`.
+fn deserialize_workspaces<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Option<Vec<String>>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Workspaces {
+        List(Vec<String>),
+        Object {
+            #[serde(default)]
+            packages: Vec<String>,
+        },
     }
 
-    let package_json_str = fs::read_to_string(package_json_path).unwrap();
-    let package_json = serde_json::from_str(&package_json_str);
+    Ok(
+        Option::<Workspaces>::deserialize(deserializer)?.map(|w| match w {
+            Workspaces::List(packages) | Workspaces::Object { packages } => packages,
+        }),
+    )
+}
 
-    Some(package_json.unwrap())
+pub fn try_load_package_json(path: &Path) -> Result<Option<PackageJson>> {
+    try_load_json_file(&path.join("package.json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::package_json::PackageJson;
+
+    #[test]
+    fn workspaces_and_missing_name() {
+        let package_json: PackageJson =
+            serde_json::from_str(r#"{ "workspaces": { "packages": ["packages/*"] } }"#).unwrap();
+        assert_eq!(package_json.name, "");
+        assert_eq!(
+            package_json.workspaces,
+            Some(vec!["packages/*".to_string()])
+        );
+
+        let package_json: PackageJson =
+            serde_json::from_str(r#"{ "name": "root", "workspaces": ["apps/*"] }"#).unwrap();
+        assert_eq!(package_json.workspaces, Some(vec!["apps/*".to_string()]));
+
+        let package_json: PackageJson = serde_json::from_str(r#"{ "name": "lib" }"#).unwrap();
+        assert_eq!(package_json.workspaces, None);
+    }
 }
@@ -0,0 +1,322 @@
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use ts_unused_symbols::{
+    DependencyKind, Dependent, DuplicateExport, DuplicateExportKind, Explanation, ImportCycle,
+    ImportKind, ImporterKind, RedundantReexport, StaleSuppression, SuppressionKind, Symbol,
+    TypeOnlyExport, TypeOnlyImport, UnlistedDependency, UnusedDependency, UnusedLocal,
+    UnusedMember,
+};
+
+pub fn print_unused_locals(unused_locals: &[UnusedLocal]) {
+    for local in unused_locals {
+        println!(
+            "{}:{}:{} - \x1b[93m{}\x1b[0m",
+            local.filename.to_str().unwrap(),
+            local.location.line,
+            local.location.column,
+            local.name,
+        );
+    }
+}
+
+pub fn print_unused_members(unused_members: &[UnusedMember]) {
+    for member in unused_members {
+        println!(
+            "{}:{}:{} - \x1b[93m{}.{}\x1b[0m",
+            member.filename.to_str().unwrap(),
+            member.location.line,
+            member.location.column,
+            member.symbol,
+            member.member,
+        );
+    }
+}
+
+pub fn print_type_only_exports(type_only_exports: &[TypeOnlyExport]) {
+    let mut by_path: BTreeMap<&PathBuf, Vec<&TypeOnlyExport>> = BTreeMap::new();
+
+    for export in type_only_exports {
+        by_path.entry(&export.filename).or_default().push(export);
+    }
+
+    for (path, exports) in by_path {
+        println!("{}:", path.to_str().unwrap());
+
+        for export in exports {
+            println!(" - \x1b[93m{}\x1b[0m", export.symbol);
+        }
+    }
+}
+
+pub fn print_type_only_imports(type_only_imports: &[TypeOnlyImport]) {
+    for import in type_only_imports {
+        println!(
+            "{}:{}:{} - \x1b[93m{}\x1b[0m",
+            import.filename.to_str().unwrap(),
+            import.location.line,
+            import.location.column,
+            import.symbol,
+        );
+    }
+}
+
+pub fn print_duplicate_exports(duplicate_exports: &[DuplicateExport]) {
+    let mut by_path: BTreeMap<&PathBuf, Vec<&DuplicateExport>> = BTreeMap::new();
+
+    for export in duplicate_exports {
+        by_path.entry(&export.filename).or_default().push(export);
+    }
+
+    for (path, exports) in by_path {
+        println!("{}:", path.to_str().unwrap());
+
+        for export in exports {
+            match &export.kind {
+                DuplicateExportKind::AmbiguousReexport(origins) => println!(
+                    " - \x1b[93m{}\x1b[0m (re-exported from {})",
+                    export.symbol,
+                    origins
+                        .iter()
+                        .map(|p| p.to_str().unwrap())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+                DuplicateExportKind::NamedAndDefault => println!(
+                    " - \x1b[93m{}\x1b[0m (exported by name and as default)",
+                    export.symbol
+                ),
+            }
+        }
+    }
+}
+
+pub fn print_redundant_reexports(redundant_reexports: &[RedundantReexport]) {
+    for reexport in redundant_reexports {
+        println!(
+            "{}:{}:{} - \x1b[93m{}\x1b[0m",
+            reexport.filename.to_str().unwrap(),
+            reexport.location.line,
+            reexport.location.column,
+            reexport.from.to_str().unwrap(),
+        );
+    }
+}
+
+pub fn print_dead_barrels(dead_barrels: &[PathBuf]) {
+    if dead_barrels.is_empty() {
+        return;
+    }
+
+    println!();
+    println!("Barrel files nobody imports");
+
+    for barrel in dead_barrels {
+        println!("\x1b[93m{}\x1b[0m", barrel.to_str().unwrap());
+    }
+}
+
+pub fn print_import_cycles(import_cycles: &[ImportCycle], kind: &str) {
+    for cycle in import_cycles {
+        println!(
+            "\x1b[93m{}\x1b[0m ({} {kind} in the cycle)",
+            cycle
+                .shortest_path
+                .iter()
+                .map(|p| p.to_str().unwrap())
+                .collect::<Vec<_>>()
+                .join(" -> "),
+            cycle.nodes.len(),
+        );
+    }
+}
+
+pub fn print_explanation(explanation: &Explanation) {
+    println!(
+        "\x1b[93m{}\x1b[0m in {}",
+        symbol_name(&explanation.symbol),
+        explanation.filename.to_str().unwrap()
+    );
+
+    if !explanation.entry_point && explanation.importers.is_empty() {
+        println!("└── not imported anywhere");
+        return;
+    }
+
+    print_explanation_children(explanation, "");
+}
+
+fn print_explanation_children(explanation: &Explanation, prefix: &str) {
+    let mut children = vec![];
+
+    if explanation.entry_point {
+        children.push(("entry point of the package".to_string(), None));
+    }
+
+    for importer in &explanation.importers {
+        let mut line = format!(
+            "{}:{}",
+            importer.filename.to_str().unwrap(),
+            importer.location.line
+        );
+
+        if let ImporterKind::Namespace(alias) = &importer.kind {
+            line.push_str(&format!(" through namespace {alias}"));
+        }
+
+        for through in &importer.through {
+            line.push_str(&format!(" <- export * in {}", through.to_str().unwrap()));
+        }
+
+        children.push((line, importer.reexport.as_ref()));
+    }
+
+    for (index, (line, reexport)) in children.iter().enumerate() {
+        let last = index == children.len() - 1;
+
+        match reexport {
+            Some(reexport) => println!(
+                "{prefix}{}{line}, re-exported as \x1b[93m{}\x1b[0m",
+                if last { "└── " } else { "├── " },
+                symbol_name(&reexport.symbol)
+            ),
+            None => println!("{prefix}{}{line}", if last { "└── " } else { "├── " }),
+        }
+
+        if let Some(reexport) = reexport {
+            let prefix = format!("{prefix}{}", if last { "    " } else { "│   " });
+            print_explanation_children(reexport, &prefix);
+        }
+    }
+}
+
+pub fn print_dependents(path: &Path, dependents: &[Dependent]) {
+    println!("Dependents of {}", path.to_str().unwrap());
+
+    let (direct, transitive): (Vec<_>, Vec<_>) = dependents.iter().partition(|d| d.depth == 1);
+
+    let mut by_symbol: BTreeMap<&Symbol, Vec<&Path>> = BTreeMap::new();
+    for dependent in dependents {
+        for symbol in &dependent.symbols {
+            by_symbol
+                .entry(symbol)
+                .or_default()
+                .push(&dependent.filename);
+        }
+    }
+
+    println!();
+    println!("Imported symbols");
+    for (symbol, filenames) in by_symbol {
+        println!(" - \x1b[93m{}\x1b[0m", symbol_name(symbol));
+        for filename in filenames {
+            println!("   - {}", filename.to_str().unwrap());
+        }
+    }
+
+    println!();
+    println!("Direct dependents");
+    for dependent in &direct {
+        println!(" - {}", dependent.filename.to_str().unwrap());
+    }
+
+    println!();
+    println!("Transitive dependents");
+    for dependent in &transitive {
+        println!(
+            " - {} ({} imports away)",
+            dependent.filename.to_str().unwrap(),
+            dependent.depth
+        );
+    }
+
+    println!();
+    println!(
+        " - {} direct and {} transitive dependents",
+        direct.len(),
+        transitive.len()
+    );
+}
+
+pub fn symbol_name(symbol: &Symbol) -> String {
+    match symbol {
+        Symbol::Symbol(s) => s.to_owned(),
+        Symbol::Default => "default".into(),
+    }
+}
+
+pub fn print_stale_suppressions(stale_suppressions: &[StaleSuppression]) {
+    for suppression in stale_suppressions {
+        println!(
+            "{}:{}:{}: \x1b[93m{}\x1b[0m suppresses nothing",
+            suppression.filename.to_str().unwrap(),
+            suppression.location.line,
+            suppression.location.column,
+            match &suppression.kind {
+                SuppressionKind::File => "ts-unused-symbols-disable".to_string(),
+                SuppressionKind::NextLine => "ts-unused-symbols-ignore-next-line".to_string(),
+                SuppressionKind::Tag(tag) => format!("@{tag}"),
+            }
+        );
+    }
+}
+
+pub fn print_unused_dependencies(unused_dependencies: &[UnusedDependency]) {
+    let mut by_package: BTreeMap<PathBuf, Vec<&UnusedDependency>> = BTreeMap::new();
+
+    for unused_dependency in unused_dependencies {
+        by_package
+            .entry(unused_dependency.package_path.join("package.json"))
+            .or_default()
+            .push(unused_dependency);
+    }
+
+    for (path, dependencies) in by_package {
+        println!("{}:", path.to_str().unwrap());
+
+        for dependency in dependencies {
+            let kind = match dependency.kind {
+                DependencyKind::Dependency => "dependencies",
+                DependencyKind::DevDependency => "devDependencies",
+                DependencyKind::PeerDependency => "peerDependencies",
+            };
+            println!(" - \x1b[93m{}\x1b[0m ({kind})", dependency.name);
+        }
+    }
+}
+
+pub fn print_unlisted_dependencies(unlisted_dependencies: &[UnlistedDependency]) {
+    for dependency in unlisted_dependencies {
+        println!(
+            "{}:{}:{} - \x1b[93m{}\x1b[0m not in {}",
+            dependency.filename.to_str().unwrap(),
+            dependency.location.line,
+            dependency.location.column,
+            dependency.name,
+            dependency
+                .package_path
+                .join("package.json")
+                .to_str()
+                .unwrap(),
+        );
+    }
+}
+
+pub fn print_imports_by_kind(imports_by_kind: &BTreeMap<ImportKind, HashSet<String>>) {
+    println!("Imports");
+
+    for (kind, imports) in imports_by_kind {
+        println!(" - {kind:?}: {}", imports.len());
+    }
+}
+
+pub fn print_unresolved_paths(unresolved_paths: &HashSet<String>) {
+    println!("Unresolved paths");
+
+    let mut unresolved_paths = unresolved_paths.iter().collect::<Vec<_>>();
+    unresolved_paths.sort();
+
+    for path in unresolved_paths {
+        println!(" - {path:?}");
+    }
+}
@@ -1,12 +1,152 @@
-use anyhow::{Context, Result};
-
-use std::collections::VecDeque;
+use std::fs;
 use std::path::{Path, PathBuf};
 
 use crate::analyze_plan::Package;
-use crate::source_map::try_load_source_map;
+use crate::error::{Error, Result};
+use crate::package_exports::{
+    package_exports_targets, resolve_package_exports, resolve_package_imports,
+    TYPESCRIPT_CONDITIONS,
+};
+use crate::package_json::{try_load_package_json, PackageJson};
+use crate::source_map::{source_map_path, try_load_source_map};
 use crate::tsconfig::TsConfig;
 
+pub(crate) const POSSIBLE_EXTENSIONS: [&str; 8] =
+    ["ts", "tsx", "js", "jsx", "mjs", "mts", "cjs", "cts"];
+
+const NODE_BUILTINS: [&str; 42] = [
+    "assert",
+    "async_hooks",
+    "buffer",
+    "child_process",
+    "cluster",
+    "console",
+    "constants",
+    "crypto",
+    "dgram",
+    "diagnostics_channel",
+    "dns",
+    "domain",
+    "events",
+    "fs",
+    "http",
+    "http2",
+    "https",
+    "inspector",
+    "module",
+    "net",
+    "os",
+    "path",
+    "perf_hooks",
+    "process",
+    "punycode",
+    "querystring",
+    "readline",
+    "repl",
+    "stream",
+    "string_decoder",
+    "sys",
+    "timers",
+    "tls",
+    "trace_events",
+    "tty",
+    "url",
+    "util",
+    "v8",
+    "vm",
+    "wasi",
+    "worker_threads",
+    "zlib",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ImportKind {
+    // ./module, ../module
+    Relative,
+
+    // resolved using tsconfig baseUrl or package.json imports
+    Alias,
+
+    // package within the monorepo
+    Workspace,
+
+    // package installed in node_modules
+    External,
+
+    // fs, node:fs
+    Builtin,
+
+    Unresolved,
+}
+
+/// Classify the import specifier, `resolved_path` is the result of `resolve_import_path`.
+pub fn classify_import(
+    current_path: &Path,
+    import_str: &str,
+    resolved_path: Option<&Path>,
+    packages: &[Package],
+) -> ImportKind {
+    if import_str.starts_with("node:") {
+        return ImportKind::Builtin;
+    }
+
+    if import_str.starts_with('.') || import_str.starts_with('#') {
+        return match (resolved_path, import_str.starts_with('.')) {
+            (Some(_), true) => ImportKind::Relative,
+            (Some(_), false) => ImportKind::Alias,
+            (None, _) => ImportKind::Unresolved,
+        };
+    }
+
+    let package_name = package_name(import_str);
+
+    if packages.iter().any(|p| p.package_json.name == package_name) {
+        return match resolved_path {
+            Some(_) => ImportKind::Workspace,
+            None => ImportKind::Unresolved,
+        };
+    }
+
+    if resolved_path.is_some() {
+        return ImportKind::Alias;
+    }
+
+    if NODE_BUILTINS.contains(&package_name) {
+        return ImportKind::Builtin;
+    }
+
+    let types_package_name = types_package_name(package_name);
+
+    let is_installed = current_path.ancestors().skip(1).any(|p| {
+        let node_modules = p.join("node_modules");
+        node_modules.join(package_name).exists() || node_modules.join(&types_package_name).exists()
+    });
+
+    if is_installed {
+        ImportKind::External
+    } else {
+        ImportKind::Unresolved
+    }
+}
+
+/// DefinitelyTyped package of the package, e.g. `@types/scope__name` for `@scope/name`.
+pub fn types_package_name(package_name: &str) -> String {
+    format!(
+        "@types/{}",
+        package_name.trim_start_matches('@').replace('/', "__")
+    )
+}
+
+/// Package name of a bare specifier, e.g. `@scope/name` for `@scope/name/sub/path`.
+pub fn package_name(import_str: &str) -> &str {
+    let segments = if import_str.starts_with('@') { 2 } else { 1 };
+
+    match import_str.match_indices('/').nth(segments - 1) {
+        Some((index, _)) => &import_str[..index],
+        None => import_str,
+    }
+}
+
 pub fn resolve_import_path(
     current_path: &Path,
     import_str: &str,
@@ -14,10 +154,22 @@ pub fn resolve_import_path(
     package_base_path: &Path,
     packages: &[Package],
 ) -> Result<Option<PathBuf>> {
+    if import_str.starts_with('#') {
+        return try_resolve_as_subpath_import(
+            current_path,
+            import_str,
+            tsconfig,
+            package_base_path,
+            packages,
+        );
+    }
+
     if let Some(path) = try_resolve_as_monorepo_package(import_str, packages)? {
         return Ok(Some(path));
     }
 
+    let compiler_options = tsconfig.to_owned().and_then(|t| t.compiler_options);
+
     let mut path = current_path.to_owned();
     path.pop();
 
@@ -25,50 +177,133 @@ pub fn resolve_import_path(
 
     if import_str.starts_with('.') {
         path.push(import_path);
-    } else if let Some(base_url) = tsconfig
-        .to_owned()
-        .and_then(|t| t.compiler_options)
-        .and_then(|c| c.base_url)
-    {
+    } else if let Some(base_url) = compiler_options.as_ref().and_then(|c| c.base_url.clone()) {
         path = package_base_path.to_owned();
         path.push(PathBuf::from(base_url));
         path.push(import_path);
+    } else {
+        return Ok(None);
     }
 
-    if path.is_dir() {
-        path.push(PathBuf::from("index"));
-    }
+    let module_suffixes = compiler_options
+        .as_ref()
+        .and_then(|c| c.module_suffixes.clone())
+        .unwrap_or_else(|| vec!["".to_string()]);
+    let allow_importing_ts_extensions = compiler_options
+        .and_then(|c| c.allow_importing_ts_extensions)
+        .unwrap_or(false);
 
-    let mut possible_extensions = VecDeque::from(["ts", "tsx", "js", "jsx", "mjs", "mts"]);
-    let filename = path
-        .file_name()
-        .context("Could not resolve file name")?
-        .to_str()
-        .context("Could not resolve file name")?
-        .to_owned();
+    let resolved_path = try_resolve_file(&path, &module_suffixes, allow_importing_ts_extensions)
+        .or_else(|| {
+            try_resolve_file(
+                &path.join("index"),
+                &module_suffixes,
+                allow_importing_ts_extensions,
+            )
+        });
 
-    while !path.exists() {
-        if let Some(extension) = possible_extensions.pop_front() {
-            let extension = extension;
-            path.set_file_name(format!("{filename}.{extension}"));
-        } else {
+    let Some(resolved_path) = resolved_path else {
+        return Ok(None);
+    };
+
+    let resolved_path = resolved_path
+        .canonicalize()
+        .map_err(|source| Error::ImportResolution {
+            specifier: import_str.to_owned(),
+            path: current_path.to_owned(),
+            source,
+        })?;
+
+    Ok(Some(resolved_path))
+}
+
+/// Follows the typescript lookup, javascript extensions (used with ESM imports)
+/// are mapped onto their typescript counterparts first and all the `moduleSuffixes`
+/// are tried for each extension.
+fn try_resolve_file(
+    path: &Path,
+    module_suffixes: &[String],
+    allow_importing_ts_extensions: bool,
+) -> Option<PathBuf> {
+    let filename = path.file_name()?.to_str()?;
+    let (stem, extension) = filename.rsplit_once('.').unwrap_or((filename, ""));
+
+    let mapped_extensions = match extension {
+        "js" => vec!["ts", "tsx", "js"],
+        "jsx" => vec!["tsx", "jsx"],
+        "mjs" => vec!["mts", "mjs"],
+        "cjs" => vec!["cts", "cjs"],
+        "ts" | "tsx" | "mts" | "cts" if allow_importing_ts_extensions => vec![extension],
+        _ => vec![],
+    };
+
+    let with_extensions = |name: &str, extensions: &[&str]| {
+        extensions
+            .iter()
+            .flat_map(|extension| {
+                module_suffixes
+                    .iter()
+                    .map(move |suffix| path.with_file_name(format!("{name}{suffix}.{extension}")))
+            })
+            .collect::<Vec<_>>()
+    };
+
+    with_extensions(stem, &mapped_extensions)
+        .into_iter()
+        .chain([path.to_owned()])
+        .chain(with_extensions(filename, &POSSIBLE_EXTENSIONS))
+        .find(|p| p.is_file())
+}
+
+/// Resolve `#`-prefixed specifiers using the `imports` field of the closest package.json.
+fn try_resolve_as_subpath_import(
+    current_path: &Path,
+    import_str: &str,
+    tsconfig: &Option<TsConfig>,
+    package_base_path: &Path,
+    packages: &[Package],
+) -> Result<Option<PathBuf>> {
+    let mut closest_package = None;
+    for p in current_path.ancestors().skip(1) {
+        if let Some(package_json) = try_load_package_json(p)? {
+            closest_package = Some((p, package_json));
             break;
         }
     }
 
-    if !path.exists() {
+    let Some((package_path, package_json)) = closest_package else {
+        return Ok(None);
+    };
+
+    let Some(target) = package_json
+        .imports
+        .and_then(|i| resolve_package_imports(&i, import_str, &TYPESCRIPT_CONDITIONS))
+    else {
         return Ok(None);
+    };
+
+    // targets not starting with `./` are package names
+    if !target.starts_with("./") {
+        return resolve_import_path(current_path, &target, tsconfig, package_base_path, packages);
+    }
+
+    if let Some(path) = resolve_package_target(package_path, &target)? {
+        return Ok(Some(path));
     }
 
-    Ok(Some(path.canonicalize().with_context(|| {
-        format!("Failed to resolve {} in {filename}", import_str.to_owned())
-    })?))
+    resolve_import_path(
+        &package_path.join("package.json"),
+        &target,
+        tsconfig,
+        package_base_path,
+        packages,
+    )
 }
 
 /// This one needs a shit ton of refactoring
 ///
 /// The idea is to try matching against a package name in the monorepo,
-/// checking the package.json types field to find the imported file,
+/// checking the package.json exports or types field to find the imported file,
 /// find corresponding .map file and parse source file from it.
 fn try_resolve_as_monorepo_package(
     import_str: &str,
@@ -76,41 +311,217 @@ fn try_resolve_as_monorepo_package(
 ) -> Result<Option<PathBuf>> {
     for package in packages.iter() {
         let package_name = &package.package_json.name;
-        if !import_str.starts_with(package_name) {
+        let Some(rest_path) = import_str.strip_prefix(package_name.as_str()) else {
+            continue;
+        };
+
+        if !rest_path.is_empty() && !rest_path.starts_with('/') {
             continue;
         }
 
+        if let Some(exports) = &package.package_json.exports {
+            let subpath = format!(".{rest_path}");
+            let target = resolve_package_exports(exports, &subpath, &TYPESCRIPT_CONDITIONS);
+
+            return match target {
+                Some(target) => resolve_package_target(&package.path, &target),
+                None => Ok(None),
+            };
+        }
+
         let mut final_path = package.path.to_owned();
 
-        if import_str == package_name {
-            let types = package.package_json.types.to_owned().context("")?;
-            final_path.push(format!("{types}.map"));
+        if rest_path.is_empty() {
+            let types = package
+                .package_json
+                .types
+                .to_owned()
+                .ok_or_else(|| Error::MissingPackageTypes(package_name.to_owned()))?;
+            final_path.push(types);
         } else {
-            let mut rest_path = &import_str[package.package_json.name.len()..];
+            final_path.push(format!("{}.d.ts", &rest_path[1..]));
+        }
 
-            if rest_path.starts_with('/') {
-                rest_path = &rest_path[1..];
-            }
-            final_path.push(format!("{rest_path}.d.ts.map"));
+        if let Some(source) = try_map_to_source(&final_path)? {
+            final_path = source;
         }
 
-        if let Some(source_map) = try_load_source_map(&final_path) {
-            if source_map.sources.len() != 1 {
-                panic!("Unexpected source {:?}", source_map.sources);
-            }
+        // declarations of a package that isn't built yet don't exist, the
+        // import is reported among the unresolved paths
+        if !final_path.exists() {
+            return Ok(None);
+        }
+
+        return final_path
+            .canonicalize()
+            .map(Some)
+            .map_err(|source| Error::ImportResolution {
+                specifier: import_str.to_owned(),
+                path: package.path.to_owned(),
+                source,
+            });
+    }
+
+    Ok(None)
+}
 
-            final_path.pop();
-            final_path.push(&source_map.sources[0]);
+/// Find source files of all the subpaths exposed by the package, either
+/// using the `exports` field or `types` and `main` fields as a fallback.
+pub fn resolve_package_entry_points(
+    path: &Path,
+    package_json: &PackageJson,
+) -> Result<Vec<PathBuf>> {
+    let Some(exports) = &package_json.exports else {
+        for target in [&package_json.types, &package_json.main]
+            .into_iter()
+            .flatten()
+        {
+            if let Some(entry_point) = resolve_package_target(path, target)? {
+                return Ok(vec![entry_point]);
+            }
         }
 
-        final_path = final_path.canonicalize()?;
+        return Ok(vec![]);
+    };
 
-        if !final_path.exists() {
-            panic!("Path {final_path:?} doesnt exist");
+    let mut entry_points = vec![];
+
+    for (subpath, target) in package_exports_targets(exports, &TYPESCRIPT_CONDITIONS) {
+        let Some((target_base, target_trailer)) = target.split_once('*') else {
+            entry_points.extend(resolve_package_target(path, &target)?);
+            continue;
+        };
+
+        for file in list_files(&path.join(target_base.trim_start_matches("./"))) {
+            let Some(file) = file.strip_prefix(path).ok().and_then(|f| f.to_str()) else {
+                continue;
+            };
+            let Some(pattern_match) = file
+                .strip_prefix(target_base.trim_start_matches("./"))
+                .and_then(|f| f.strip_suffix(target_trailer))
+            else {
+                continue;
+            };
+
+            // the subpath can be excluded by a more specific pattern
+            let resolved = resolve_package_exports(
+                exports,
+                &subpath.replace('*', pattern_match),
+                &TYPESCRIPT_CONDITIONS,
+            )
+            .filter(|resolved| *resolved == target.replace('*', pattern_match));
+
+            if let Some(resolved) = resolved {
+                entry_points.extend(resolve_package_target(path, &resolved)?);
+            }
         }
+    }
+
+    Ok(entry_points)
+}
+
+/// Map a target from package.json onto the typescript source. Targets can point
+/// to the source directly, to a declaration file with a corresponding
+/// declaration map or to a javascript file next to its declaration file.
+fn resolve_package_target(package_path: &Path, target: &str) -> Result<Option<PathBuf>> {
+    let path = package_path.join(target.trim_start_matches("./"));
+    let Some(filename) = path.file_name().and_then(|f| f.to_str()) else {
+        return Ok(None);
+    };
+
+    let declaration_path = [
+        (".js", ".d.ts"),
+        (".mjs", ".d.mts"),
+        (".cjs", ".d.cts"),
+        (".jsx", ".d.ts"),
+    ]
+    .into_iter()
+    .find_map(|(extension, declaration_extension)| {
+        filename
+            .strip_suffix(extension)
+            .map(|f| path.with_file_name(format!("{f}{declaration_extension}")))
+    })
+    .unwrap_or_else(|| path.to_owned());
+
+    Ok(try_map_to_source(&declaration_path)?
+        .or(Some(path))
+        .filter(|p| p.exists())
+        .and_then(|p| p.canonicalize().ok()))
+}
+
+/// Source file of the declaration file according to its declaration map.
+fn try_map_to_source(declaration_path: &Path) -> Result<Option<PathBuf>> {
+    let source_map_path = source_map_path(declaration_path);
+
+    let Some(source_map) = try_load_source_map(&source_map_path)? else {
+        return Ok(None);
+    };
+
+    Ok(Some(match source_map.resolved_sources().as_slice() {
+        [source] => source_map_path.with_file_name(source),
+        // declarations bundled from multiple sources stay as they are until
+        // the imported symbols are known and can be mapped onto their sources,
+        // see `DeclarationMap`
+        _ => declaration_path.to_owned(),
+    }))
+}
+
+fn list_files(path: &Path) -> Vec<PathBuf> {
+    let mut directory = path.to_owned();
 
-        return Ok(Some(final_path));
+    // the pattern can end in the middle of a file name, e.g. `./src/utils-*.ts`
+    if !directory.is_dir() {
+        directory.pop();
     }
 
-    Ok(None)
+    let Ok(dir) = fs::read_dir(directory) else {
+        return vec![];
+    };
+
+    dir.flatten()
+        .flat_map(|f| {
+            let path = f.path();
+
+            if path.is_dir() {
+                list_files(&path)
+            } else {
+                vec![path]
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashSet, path::PathBuf};
+
+    use crate::{
+        package_json::try_load_package_json,
+        resolve_import_path::{package_name, resolve_package_entry_points},
+    };
+
+    #[test]
+    fn package_exports_entry_points() {
+        let path = PathBuf::from("./tests/package-exports/packages/lib");
+        let package_json = try_load_package_json(&path).unwrap().unwrap();
+
+        assert_eq!(
+            resolve_package_entry_points(&path, &package_json)
+                .unwrap()
+                .into_iter()
+                .collect::<HashSet<_>>(),
+            HashSet::from([
+                path.join("src/index.ts").canonicalize().unwrap(),
+                path.join("src/utils/format.ts").canonicalize().unwrap(),
+            ])
+        );
+    }
+
+    #[test]
+    fn package_names() {
+        assert_eq!(package_name("lodash"), "lodash");
+        assert_eq!(package_name("lodash/fp"), "lodash");
+        assert_eq!(package_name("@scope/name"), "@scope/name");
+        assert_eq!(package_name("@scope/name/sub/path"), "@scope/name");
+    }
 }
@@ -1,21 +1,294 @@
-use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use serde::Deserialize;
 
-#[derive(Debug, Deserialize, Clone)]
+use crate::error::Result;
+use crate::json_file::try_load_json_file;
+
+#[derive(Debug, Deserialize, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct SourceMap {
+    pub source_root: Option<String>,
+    #[serde(default)]
     pub sources: Vec<String>,
+    #[serde(default)]
+    pub names: Vec<String>,
+    #[serde(default)]
+    pub mappings: String,
+    pub sections: Option<Vec<SourceMapSection>>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct SourceMapSection {
+    pub offset: SourceMapOffset,
+    pub map: SourceMap,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct SourceMapOffset {
+    pub line: u32,
+    pub column: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mapping {
+    pub generated_line: u32,
+    pub generated_column: u32,
+    pub original: Option<OriginalPosition>,
+}
+
+/// Lines and columns are zero-based as in the source map itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OriginalPosition {
+    pub source: String,
+    pub line: u32,
+    pub column: u32,
+    pub name: Option<String>,
+}
+
+impl SourceMap {
+    /// Sources prefixed with the `sourceRoot`, including sources of all the sections.
+    pub fn resolved_sources(&self) -> Vec<String> {
+        match &self.sections {
+            Some(sections) => sections
+                .iter()
+                .flat_map(|s| s.map.resolved_sources())
+                .collect(),
+            None => self
+                .sources
+                .iter()
+                .map(|s| self.resolve_source(s))
+                .collect(),
+        }
+    }
+
+    /// Decode all the mappings, mappings of sections are shifted by their offsets.
+    /// Fails with a message on a malformed segment.
+    pub fn decode_mappings(&self) -> std::result::Result<Vec<Mapping>, String> {
+        if let Some(sections) = &self.sections {
+            let mut mappings = vec![];
+
+            for section in sections {
+                mappings.extend(section.map.decode_mappings()?.into_iter().map(|mut m| {
+                    if m.generated_line == 0 {
+                        m.generated_column += section.offset.column;
+                    }
+                    m.generated_line += section.offset.line;
+                    m
+                }));
+            }
+
+            return Ok(mappings);
+        }
+
+        let mut mappings = vec![];
+        let mut source = 0;
+        let mut original_line = 0;
+        let mut original_column = 0;
+        let mut name = 0;
+
+        for (generated_line, line) in self.mappings.split(';').enumerate() {
+            let mut generated_column = 0;
+
+            for segment in line.split(',').filter(|s| !s.is_empty()) {
+                let fields = decode_vlq_segment(segment)?;
+
+                generated_column += fields[0];
+
+                let original = if fields.len() >= 4 {
+                    source += fields[1];
+                    original_line += fields[2];
+                    original_column += fields[3];
+
+                    if fields.len() >= 5 {
+                        name += fields[4];
+                    }
+
+                    self.sources.get(source as usize).map(|s| OriginalPosition {
+                        source: self.resolve_source(s),
+                        line: original_line as u32,
+                        column: original_column as u32,
+                        name: (fields.len() >= 5)
+                            .then(|| self.names.get(name as usize).cloned())
+                            .flatten(),
+                    })
+                } else {
+                    None
+                };
+
+                mappings.push(Mapping {
+                    generated_line: generated_line as u32,
+                    generated_column: generated_column as u32,
+                    original,
+                });
+            }
+        }
+
+        Ok(mappings)
+    }
+
+    fn resolve_source(&self, source: &str) -> String {
+        match self.source_root.as_deref() {
+            Some(root) if !root.is_empty() => format!("{}/{source}", root.trim_end_matches('/')),
+            _ => source.to_owned(),
+        }
+    }
+}
+
+/// Find the original position of the closest mapping on the same line
+/// starting at or before the `column`.
+pub fn find_original_position(
+    mappings: &[Mapping],
+    line: u32,
+    column: u32,
+) -> Option<OriginalPosition> {
+    let mappings = mappings
+        .iter()
+        .filter(|m| m.generated_line == line && m.original.is_some())
+        .collect::<Vec<_>>();
+
+    mappings
+        .iter()
+        .filter(|m| m.generated_column <= column)
+        .max_by_key(|m| m.generated_column)
+        .or_else(|| mappings.iter().min_by_key(|m| m.generated_column))
+        .and_then(|m| m.original.clone())
 }
 
-pub fn try_load_source_map(path: &Path) -> Option<SourceMap> {
-    if !path.exists() {
-        return None;
+/// Source maps encode 32-bit values, more continuation digits than that
+/// would overflow the shift.
+const MAX_VLQ_SHIFT: u32 = 30;
+
+fn decode_vlq_segment(segment: &str) -> std::result::Result<Vec<i64>, String> {
+    let mut fields = vec![];
+    let mut value: i64 = 0;
+    let mut shift = 0;
+
+    for c in segment.bytes() {
+        let digit = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return Err(format!("invalid character {:?} in {segment:?}", c as char)),
+        } as i64;
+
+        value += (digit & 0b11111) << shift;
+
+        if digit & 0b100000 != 0 {
+            shift += 5;
+
+            if shift > MAX_VLQ_SHIFT {
+                return Err(format!("value out of range in {segment:?}"));
+            }
+
+            continue;
+        }
+
+        let negative = value & 1 == 1;
+        value >>= 1;
+        fields.push(if negative { -value } else { value });
+
+        value = 0;
+        shift = 0;
     }
 
-    let source_map_str = fs::read_to_string(path).unwrap();
-    let source_map = serde_json::from_str(&source_map_str);
+    if shift != 0 {
+        return Err(format!("unterminated value in {segment:?}"));
+    }
+
+    Ok(fields)
+}
 
-    Some(source_map.unwrap())
+/// Source map of a generated file, `index.d.ts.map` next to `index.d.ts`.
+pub fn source_map_path(path: &Path) -> PathBuf {
+    let mut source_map_path = path.to_owned().into_os_string();
+    source_map_path.push(".map");
+    PathBuf::from(source_map_path)
+}
+
+pub fn try_load_source_map(path: &Path) -> Result<Option<SourceMap>> {
+    try_load_json_file(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::source_map::{
+        decode_vlq_segment, find_original_position, OriginalPosition, SourceMap,
+    };
+
+    #[test]
+    fn decode_mappings() {
+        let source_map: SourceMap = serde_json::from_str(
+            r#"{
+                "version": 3,
+                "sourceRoot": "../src",
+                "sources": ["a.ts", "b.ts"],
+                "names": ["b"],
+                "mappings": "AAAA,eAAO,MAAM,CAAC;ACAd,eAAOA,MAAM"
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            find_original_position(&source_map.decode_mappings().unwrap(), 0, 22),
+            Some(OriginalPosition {
+                source: "../src/a.ts".to_string(),
+                line: 0,
+                column: 14,
+                name: None
+            })
+        );
+        assert_eq!(
+            find_original_position(&source_map.decode_mappings().unwrap(), 1, 16),
+            Some(OriginalPosition {
+                source: "../src/b.ts".to_string(),
+                line: 0,
+                column: 7,
+                name: Some("b".to_string())
+            })
+        );
+    }
+
+    #[test]
+    fn sections() {
+        let source_map: SourceMap = serde_json::from_str(
+            r#"{
+                "version": 3,
+                "sections": [
+                    { "offset": { "line": 0, "column": 0 }, "map": { "sources": ["a.ts"], "mappings": "AAAA" } },
+                    { "offset": { "line": 2, "column": 4 }, "map": { "sources": ["b.ts"], "mappings": "AACA" } }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(source_map.resolved_sources(), vec!["a.ts", "b.ts"]);
+        assert_eq!(
+            find_original_position(&source_map.decode_mappings().unwrap(), 2, 10)
+                .map(|p| (p.source, p.line)),
+            Some(("b.ts".to_string(), 1))
+        );
+        assert_eq!(
+            find_original_position(&source_map.decode_mappings().unwrap(), 1, 0),
+            None
+        );
+    }
+
+    #[test]
+    fn malformed_mappings() {
+        assert_eq!(decode_vlq_segment("gBAAA"), Ok(vec![16, 0, 0, 0]));
+        assert!(decode_vlq_segment("A!").is_err());
+        assert!(decode_vlq_segment("g").is_err());
+        assert!(decode_vlq_segment("gggggggggggggggA").is_err());
+
+        let source_map = SourceMap {
+            sources: vec!["a.ts".to_string()],
+            mappings: "AAAA;////////////////B".to_string(),
+            ..SourceMap::default()
+        };
+
+        assert!(source_map.decode_mappings().is_err());
+    }
 }
@@ -0,0 +1,297 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::analyze_package::AnalyzedPackage;
+use crate::analyzed_module::AnalyzedModule;
+use crate::find_import_cycles::ImportCycle;
+use crate::find_unused_exports::{DuplicateExport, DuplicateExportKind, Symbol, UnusedExport};
+use crate::module_symbols::{FindingKind, Location, ReexportKind, Suppression, SuppressionKind};
+
+/// Suppression comment which no longer suppresses any finding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StaleSuppression {
+    pub filename: PathBuf,
+    pub location: Location,
+    pub kind: SuppressionKind,
+}
+
+/// Suppression comments of the analyzed modules, remembering the ones which
+/// suppressed a finding and the kinds of findings checked.
+pub struct Suppressions<'a> {
+    modules: HashMap<&'a Path, &'a AnalyzedModule<PathBuf>>,
+
+    // JSDoc tags marking exports as used
+    tags: &'a [String],
+    used: HashSet<(&'a Path, &'a Suppression)>,
+    checked: HashSet<FindingKind>,
+}
+
+impl<'a> Suppressions<'a> {
+    pub fn new(analyzed_packages: &'a [AnalyzedPackage], tags: &'a [String]) -> Self {
+        Self {
+            modules: analyzed_packages
+                .iter()
+                .flat_map(|p| p.modules.iter())
+                .map(|(path, module)| (path.as_path(), module))
+                .collect(),
+            tags,
+            used: HashSet::new(),
+            checked: HashSet::new(),
+        }
+    }
+
+    /// Record that the findings of the kind were checked, even when there were none.
+    pub fn check(&mut self, kind: FindingKind) {
+        self.checked.insert(kind);
+    }
+
+    /// Whether a comment suppresses the finding on the line of the module.
+    pub fn suppresses(&mut self, kind: FindingKind, path: &Path, line: usize) -> bool {
+        self.suppress(kind, path, &[line])
+    }
+
+    /// Whether a comment suppresses all the findings of the kind in the module.
+    pub fn suppresses_file(&mut self, kind: FindingKind, path: &Path) -> bool {
+        self.suppress(kind, path, &[])
+    }
+
+    /// Whether a comment or a JSDoc tag suppresses the unused export, on the
+    /// lines of the declarations it exports.
+    pub fn suppresses_export(&mut self, unused_export: &UnusedExport) -> bool {
+        let name = match &unused_export.symbol {
+            Symbol::Symbol(s) => s.as_str(),
+            Symbol::Default => "default",
+        };
+
+        self.suppresses_symbol(FindingKind::UnusedExports, &unused_export.filename, name)
+    }
+
+    /// Whether a comment or a JSDoc tag suppresses the finding about the
+    /// export, on the lines of the declarations exported under the name.
+    pub fn suppresses_symbol(&mut self, kind: FindingKind, path: &Path, name: &str) -> bool {
+        let lines = self.lines(path, |module| {
+            module
+                .symbols
+                .declarations
+                .iter()
+                .filter(|d| d.exported_as.contains(name))
+                .map(|d| d.location.line)
+                .collect()
+        });
+
+        self.suppress(kind, path, &lines)
+    }
+
+    /// Whether a comment suppresses the duplicate export, on the lines of the
+    /// `export *` statements or of the declaration exported twice.
+    pub fn suppresses_duplicate_export(&mut self, duplicate_export: &DuplicateExport) -> bool {
+        let lines = self.lines(
+            &duplicate_export.filename,
+            |module| match duplicate_export.kind {
+                DuplicateExportKind::AmbiguousReexport(_) => module
+                    .symbols
+                    .reexports
+                    .iter()
+                    .filter(|r| r.kind == ReexportKind::All)
+                    .map(|r| r.location.line)
+                    .collect(),
+                DuplicateExportKind::NamedAndDefault => module
+                    .symbols
+                    .declarations
+                    .iter()
+                    .filter(|d| d.name == duplicate_export.symbol)
+                    .map(|d| d.location.line)
+                    .collect(),
+            },
+        );
+
+        self.suppress(
+            FindingKind::DuplicateExports,
+            &duplicate_export.filename,
+            &lines,
+        )
+    }
+
+    /// Whether a comment suppresses the import cycle, on the lines of any of
+    /// the imports and re-exports closing it.
+    pub fn suppresses_cycle(&mut self, import_cycle: &ImportCycle) -> bool {
+        let mut suppressed = false;
+
+        for edge in import_cycle.shortest_path.windows(2) {
+            let (from, to) = (&edge[0], &edge[1]);
+            let lines = self.lines(from, |module| {
+                let imports = module.symbols.imports.iter().map(|i| (&i.from, i.location));
+                let reexports = module
+                    .symbols
+                    .reexports
+                    .iter()
+                    .map(|r| (&r.from, r.location));
+
+                imports
+                    .chain(reexports)
+                    .filter(|(path, _)| *path == to)
+                    .map(|(_, location)| location.line)
+                    .collect()
+            });
+
+            suppressed |= self.suppress(FindingKind::ImportCycles, from, &lines);
+        }
+
+        suppressed
+    }
+
+    /// Comments targeting only checked kinds of findings which suppressed none
+    /// of them, JSDoc tags document the API and are never stale.
+    pub fn stale(&self) -> Vec<StaleSuppression> {
+        let mut stale = self
+            .modules
+            .iter()
+            .flat_map(|(path, module)| {
+                module
+                    .symbols
+                    .suppressions
+                    .iter()
+                    .map(move |suppression| (*path, suppression))
+            })
+            .filter(|(_, suppression)| !matches!(suppression.kind, SuppressionKind::Tag(_)))
+            .filter(|(_, suppression)| {
+                if suppression.targets.is_empty() {
+                    FindingKind::ALL.iter().all(|k| self.checked.contains(k))
+                } else {
+                    suppression.targets.iter().all(|k| self.checked.contains(k))
+                }
+            })
+            .filter(|used| !self.used.contains(used))
+            .map(|(path, suppression)| StaleSuppression {
+                filename: path.to_owned(),
+                location: suppression.location,
+                kind: suppression.kind.to_owned(),
+            })
+            .collect::<Vec<_>>();
+
+        stale.sort_by(|a, b| (&a.filename, a.location).cmp(&(&b.filename, b.location)));
+        stale
+    }
+
+    fn lines(
+        &self,
+        path: &Path,
+        lines: impl FnOnce(&AnalyzedModule<PathBuf>) -> Vec<usize>,
+    ) -> Vec<usize> {
+        self.modules
+            .get(path)
+            .map(|module| lines(module))
+            .unwrap_or_default()
+    }
+
+    fn suppress(&mut self, kind: FindingKind, path: &Path, lines: &[usize]) -> bool {
+        let Some((path, module)) = self.modules.get_key_value(path) else {
+            return false;
+        };
+
+        let mut suppressed = false;
+
+        for suppression in &module.symbols.suppressions {
+            let covers = || {
+                lines
+                    .iter()
+                    .any(|l| (suppression.first_line..=suppression.last_line).contains(l))
+            };
+            let targets = suppression.targets.is_empty() || suppression.targets.contains(&kind);
+            let applies = match &suppression.kind {
+                SuppressionKind::File => targets,
+                SuppressionKind::NextLine => targets && covers(),
+                SuppressionKind::Tag(tag) => {
+                    kind == FindingKind::UnusedExports && self.tags.contains(tag) && covers()
+                }
+            };
+
+            if applies {
+                self.used.insert((path, suppression));
+                suppressed = true;
+            }
+        }
+
+        suppressed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        find_unused_exports::{find_unused_exports, Symbol},
+        find_unused_locals::find_unused_locals,
+        module_symbols::{FindingKind, SuppressionKind},
+        suppressions::{StaleSuppression, Suppressions},
+        test_utils::analyze_monorepo,
+    };
+
+    fn lines(stale: Vec<StaleSuppression>) -> Vec<(String, usize, SuppressionKind)> {
+        stale
+            .into_iter()
+            .map(|s| {
+                (
+                    s.filename.file_name().unwrap().to_str().unwrap().to_owned(),
+                    s.location.line,
+                    s.kind,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn suppressions() {
+        let analyzed_packages = analyze_monorepo("./tests/suppressions");
+
+        let tags = vec!["public".to_string()];
+        let mut suppressions = Suppressions::new(&analyzed_packages, &tags);
+
+        suppressions.check(FindingKind::UnusedExports);
+        let unused_exports = find_unused_exports(&analyzed_packages)
+            .into_iter()
+            .filter(|e| !suppressions.suppresses_export(e))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            unused_exports
+                .iter()
+                .map(|e| e.symbol.to_owned())
+                .collect::<Vec<_>>(),
+            vec![
+                Symbol::Symbol("internal".to_string()),
+                Symbol::Symbol("misspelled".to_string()),
+                Symbol::Symbol("reported".to_string()),
+                Symbol::Symbol("tooFar".to_string()),
+                Symbol::Symbol("wrongTarget".to_string()),
+            ]
+        );
+
+        // comments targeting all the findings are judged only once all were checked
+        assert_eq!(
+            lines(suppressions.stale()),
+            vec![("lib.ts".to_string(), 32, SuppressionKind::NextLine)]
+        );
+
+        suppressions.check(FindingKind::UnusedLocals);
+        let unused_locals = find_unused_locals(&analyzed_packages, &unused_exports)
+            .into_iter()
+            .filter(|l| {
+                !suppressions.suppresses(FindingKind::UnusedLocals, &l.filename, l.location.line)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(unused_locals, vec![]);
+
+        for kind in FindingKind::ALL {
+            suppressions.check(kind);
+        }
+        assert_eq!(
+            lines(suppressions.stale()),
+            vec![
+                ("lib.ts".to_string(), 15, SuppressionKind::NextLine),
+                ("lib.ts".to_string(), 32, SuppressionKind::NextLine),
+                ("lib.ts".to_string(), 35, SuppressionKind::NextLine),
+                ("lib.ts".to_string(), 38, SuppressionKind::NextLine),
+                ("stale.ts".to_string(), 1, SuppressionKind::File),
+            ]
+        );
+    }
+}
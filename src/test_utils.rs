@@ -0,0 +1,11 @@
+use std::path::Path;
+
+use crate::analyze_package::{analyze_packages, AnalyzedPackage};
+use crate::analyze_plan::prepare_analyze_plan;
+
+/// Analyze every package of the fixture, a single package or a monorepo,
+/// with the default options.
+pub fn analyze_monorepo(path: &str) -> Vec<AnalyzedPackage> {
+    let analyze_plan = prepare_analyze_plan(Path::new(path)).unwrap();
+    analyze_packages(&analyze_plan.packages, &Default::default()).unwrap()
+}
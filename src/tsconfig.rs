@@ -1,10 +1,10 @@
-use std::{
-    fs,
-    path::{Path, PathBuf},
-};
+use std::path::{Path, PathBuf};
 
 use serde::Deserialize;
 
+use crate::error::Result;
+use crate::json_file::try_load_json_file;
+
 #[derive(Debug, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct TsConfig {
@@ -16,20 +16,10 @@ pub struct TsConfig {
 pub struct TsConfigCompilerOptions {
     pub base_url: Option<String>,
     pub out_dir: Option<PathBuf>,
+    pub module_suffixes: Option<Vec<String>>,
+    pub allow_importing_ts_extensions: Option<bool>,
 }
 
-pub fn try_load_tsconfig(path: &Path) -> Option<TsConfig> {
-    let tsconfig_filename = "tsconfig.json";
-
-    let mut tsconfig_path = path.to_owned();
-    tsconfig_path.push(PathBuf::from(tsconfig_filename));
-
-    if !tsconfig_path.exists() {
-        return None;
-    }
-
-    let tsconfig_str = fs::read_to_string(tsconfig_path).unwrap();
-    let tsconfig = serde_json::from_str(&tsconfig_str);
-
-    Some(tsconfig.unwrap())
+pub fn try_load_tsconfig(path: &Path) -> Result<Option<TsConfig>> {
+    try_load_json_file(&path.join("tsconfig.json"))
 }
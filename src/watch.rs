@@ -0,0 +1,178 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+
+use ts_unused_symbols::incremental::{build_path, is_source_file, package_of, reanalyze_modules};
+use ts_unused_symbols::{AnalyzeOptions, AnalyzedPackage, Export, Package, TsConfig};
+
+// editors tend to write a file in several steps
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// Keep the analyzed packages up to date with the changes on disk and print
+/// the findings which appeared or disappeared after each change.
+pub fn watch(
+    path: &Path,
+    packages: &[Package],
+    options: &AnalyzeOptions,
+    mut analyzed_packages: Vec<AnalyzedPackage>,
+    findings: impl Fn(&[AnalyzedPackage]) -> BTreeSet<String>,
+) -> Result<()> {
+    let path = path
+        .canonicalize()
+        .context("Couldnt resolve the watched path")?;
+    let (sender, receiver) = channel();
+
+    let mut watcher = notify::recommended_watcher(sender).context("Couldnt start watching")?;
+    watcher
+        .watch(&path, RecursiveMode::Recursive)
+        .with_context(|| format!("Couldnt watch {path:?}"))?;
+
+    let mut previous_findings = findings(&analyzed_packages);
+
+    println!();
+    println!("Watching {} for changes", path.to_str().unwrap());
+
+    loop {
+        let known_paths = analyzed_packages
+            .iter()
+            .flat_map(|p| p.modules.keys())
+            .collect::<HashSet<_>>();
+
+        // new files within the build output of their package aren't sources
+        let is_new_source_file = |p: &Path| {
+            let out_dir = package_of(&analyzed_packages, p).and_then(|index| {
+                let path = &analyzed_packages[index].path;
+                build_path(path, &tsconfig_of(packages, path))
+            });
+
+            is_source_file(p, options.exclude_patterns(), &out_dir)
+        };
+
+        let changed_paths = next_changes(&receiver)?
+            .into_iter()
+            .filter(|p| known_paths.contains(p) || is_new_source_file(p))
+            .collect::<HashSet<_>>();
+        let affected_paths = with_dependents(&analyzed_packages, &changed_paths);
+
+        let mut updated = false;
+
+        for (index, paths) in group_by_package(&analyzed_packages, &affected_paths) {
+            let analyzed_package = &mut analyzed_packages[index];
+            let tsconfig = tsconfig_of(packages, &analyzed_package.path);
+
+            reanalyze_modules(analyzed_package, &paths, &tsconfig, options, packages)?;
+
+            for path in &paths {
+                if let Some(message) = analyzed_package.unparsed_files.get(path) {
+                    eprintln!("Failed to parse {path:?}: {message}, keeping the previous analysis");
+                }
+            }
+
+            updated = true;
+        }
+
+        if !updated {
+            continue;
+        }
+
+        let current_findings = findings(&analyzed_packages);
+
+        println!();
+        for finding in current_findings.difference(&previous_findings) {
+            println!("\x1b[93m+ {finding}\x1b[0m");
+        }
+        for finding in previous_findings.difference(&current_findings) {
+            println!("\x1b[92m- {finding}\x1b[0m");
+        }
+        println!(
+            " - {} findings after changes in {} files",
+            current_findings.len(),
+            changed_paths.len()
+        );
+
+        previous_findings = current_findings;
+    }
+}
+
+fn tsconfig_of(packages: &[Package], path: &Path) -> Option<TsConfig> {
+    packages
+        .iter()
+        .find(|p| p.path == path)
+        .and_then(|p| p.tsconfig.clone())
+}
+
+/// Block until something changes, then collect the changes following shortly after.
+fn next_changes(receiver: &Receiver<notify::Result<Event>>) -> Result<HashSet<PathBuf>> {
+    let mut changed_paths = HashSet::new();
+
+    // reading the files while analyzing them triggers access events
+    let is_change = |event: &Event| !matches!(event.kind, EventKind::Access(_));
+
+    while changed_paths.is_empty() {
+        let event = receiver.recv().context("File watcher stopped")??;
+        if is_change(&event) {
+            changed_paths.extend(event.paths);
+        }
+    }
+
+    loop {
+        match receiver.recv_timeout(DEBOUNCE) {
+            Ok(event) => {
+                let event = event.context("File watcher failed")?;
+                if is_change(&event) {
+                    changed_paths.extend(event.paths);
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => return Ok(changed_paths),
+            Err(RecvTimeoutError::Disconnected) => anyhow::bail!("File watcher stopped"),
+        }
+    }
+}
+
+/// Changed paths together with the modules importing or re-exporting them,
+/// whose resolved imports may be affected.
+fn with_dependents(
+    analyzed_packages: &[AnalyzedPackage],
+    changed_paths: &HashSet<PathBuf>,
+) -> HashSet<PathBuf> {
+    let mut affected_paths = changed_paths.clone();
+
+    for module in analyzed_packages.iter().flat_map(|p| p.modules.values()) {
+        let imports = module.symbols.imports.iter().map(|i| &i.from);
+        let reexports = module.symbols.reexports.iter().map(|r| &r.from);
+        let all_exports = module.symbols.exports.iter().filter_map(|e| match e {
+            Export::AllFrom(from) => Some(from),
+            _ => None,
+        });
+
+        if imports
+            .chain(reexports)
+            .chain(all_exports)
+            .any(|from| changed_paths.contains(from))
+        {
+            affected_paths.insert(module.path.to_owned());
+        }
+    }
+
+    affected_paths
+}
+
+/// Paths by the package they belong to, paths outside of all the packages are dropped.
+fn group_by_package(
+    analyzed_packages: &[AnalyzedPackage],
+    paths: &HashSet<PathBuf>,
+) -> HashMap<usize, HashSet<PathBuf>> {
+    let mut result: HashMap<usize, HashSet<PathBuf>> = HashMap::new();
+
+    for path in paths {
+        if let Some(index) = package_of(analyzed_packages, path) {
+            result.entry(index).or_default().insert(path.to_owned());
+        }
+    }
+
+    result
+}
@@ -0,0 +1,3 @@
+import { format } from "lib";
+
+export const run = () => format(" app ");
@@ -0,0 +1,2 @@
+export const format = (value: string) => value.trim();
+export const parse = (value: string) => value.split(",");
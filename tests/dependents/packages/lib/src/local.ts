@@ -0,0 +1,3 @@
+import * as formatting from "./format";
+
+formatting.parse("a,b");
@@ -0,0 +1,5 @@
+export const Button = () => "button";
+
+export const format = (value: number) => `${value}`;
+
+export default Button;
@@ -0,0 +1,6 @@
+export * from "./shared";
+export * from "./button";
+export * from "./link";
+export * from "./theme";
+
+export const size = 2;
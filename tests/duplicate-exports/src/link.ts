@@ -0,0 +1,5 @@
+const Link = () => "link";
+
+export const format = (value: string) => value;
+
+export { Link, Link as default };
@@ -0,0 +1,9 @@
+import { utils } from "./utils.js";
+import { Component } from "./component.jsx";
+import { platform } from "./platform.js";
+import { helpers } from "./helpers.ts";
+import { lib } from "./lib.mjs";
+import { common } from "./common.cjs";
+import { legacy } from "./legacy";
+
+console.log(utils, Component, platform, helpers, lib, common, legacy);
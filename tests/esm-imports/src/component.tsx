@@ -0,0 +1 @@
+export const Component = () => <div />;
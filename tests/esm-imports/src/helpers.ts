@@ -0,0 +1 @@
+export const helpers = 1;
@@ -0,0 +1,5 @@
+import c from './a';
+import * as ns from './a';
+import { a } from './index';
+
+c(ns.b, a);
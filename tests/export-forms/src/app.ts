@@ -0,0 +1,6 @@
+import { values } from "./barrel";
+
+{
+  using resource = { [Symbol.dispose]() {} };
+  console.log(values, resource);
+}
@@ -0,0 +1 @@
+export * as values from "./values";
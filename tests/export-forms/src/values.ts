@@ -0,0 +1,5 @@
+const o = { a: 1, b: [2], c: 3 };
+
+export const { a, b: [b], ...rest } = o;
+
+export as namespace Values;
@@ -0,0 +1,23 @@
+export function internal() {
+  return 1;
+}
+
+export function used() {
+  return internal();
+}
+
+export function unused() {
+  return 2;
+}
+
+const local = 3;
+
+console.log(local);
+
+export { local as renamed };
+
+export function defaultToo() {
+  return 4;
+}
+
+export default defaultToo;
@@ -0,0 +1,3 @@
+export * from "./a";
+
+export const c = () => 1;
@@ -0,0 +1,5 @@
+import { a } from "./a";
+
+export const d = 2;
+
+export const callA = () => a();
@@ -0,0 +1,5 @@
+import { callA } from "./d";
+import { create } from "./types";
+
+callA();
+create();
@@ -0,0 +1,5 @@
+import type { Kind } from "./types";
+
+export class Model {
+  kind?: Kind;
+}
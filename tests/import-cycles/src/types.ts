@@ -0,0 +1,5 @@
+import { Model } from "./model";
+
+export type Kind = "a" | "b";
+
+export const create = () => new Model();
@@ -0,0 +1,7 @@
+import { readFileSync } from "fs";
+import { join } from "node:path";
+import { debounce } from "lodash";
+import { missing } from "not-installed";
+import { local } from "./local";
+
+console.log(readFileSync, join, debounce, missing, local);
@@ -0,0 +1,4 @@
+import { a, b as run } from "lib";
+
+run();
+console.log(a);
@@ -0,0 +1,4 @@
+declare const a = 1;
+declare const unusedA = 2;
+declare function b(): void;
+export { a, b, unusedA };
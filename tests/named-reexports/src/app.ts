@@ -0,0 +1,4 @@
+import { minus } from "./barrel";
+import { add } from "./math";
+
+console.log(add(1, minus(2, 1)));
@@ -0,0 +1,2 @@
+export { sub as minus, mul } from "./math";
+export { default as format } from "./format";
@@ -0,0 +1 @@
+export default (value: number) => value.toFixed(2);
@@ -0,0 +1,5 @@
+export const add = (a: number, b: number) => a + b;
+
+export const sub = (a: number, b: number) => a - b;
+
+export const mul = (a: number, b: number) => a * b;
@@ -0,0 +1,4 @@
+import { value } from "lib";
+import { format } from "lib/utils/format";
+
+console.log(format(value));
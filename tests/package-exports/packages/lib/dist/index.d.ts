@@ -0,0 +1,3 @@
+export declare const value = 1;
+export declare const unusedValue = 2;
+//# sourceMappingURL=index.d.ts.map
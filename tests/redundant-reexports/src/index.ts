@@ -0,0 +1,3 @@
+import { add } from "./utils";
+
+console.log(add(1, 2));
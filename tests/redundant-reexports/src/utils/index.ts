@@ -0,0 +1,3 @@
+export * from "./math";
+export * from "./strings";
+export { sub as minus } from "./math";
@@ -0,0 +1,2 @@
+export { b } from "./b";
+export const a = 1;
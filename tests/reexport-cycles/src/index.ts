@@ -0,0 +1 @@
+export { a, b } from "./a";
@@ -0,0 +1,3 @@
+export * from "./b";
+export * from "./c";
+export const shared = 0;
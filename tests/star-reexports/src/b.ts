@@ -0,0 +1,3 @@
+export * from "./a";
+export const fromB = 1;
+export const shared = 1;
@@ -0,0 +1,2 @@
+export const fromC = 2;
+export const shared = 2;
@@ -0,0 +1,3 @@
+import { fromB, fromC, shared } from "./a";
+
+console.log(fromB, fromC, shared);
@@ -0,0 +1,6 @@
+import { config } from "#config";
+import { log } from "#utils/log";
+
+if (config.debug) {
+  log("debug");
+}
@@ -0,0 +1,2 @@
+/* ts-unused-symbols-disable */
+export const disabled = 1;
@@ -0,0 +1,2 @@
+// ts-unused-symbols-disable -- generated code
+export const generated = 1;
@@ -0,0 +1,4 @@
+import { used, alsoUsed, targetedUsed } from "./lib";
+import { imported } from "./stale";
+
+console.log(used, alsoUsed, targetedUsed, imported);
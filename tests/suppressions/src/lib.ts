@@ -0,0 +1,40 @@
+export const used = 1;
+
+// ts-unused-symbols-ignore-next-line
+export const ignored = 2;
+
+/**
+ * Part of the public API.
+ * @public
+ */
+export function tagged() {}
+
+/** @internal */
+export const internal = 3;
+
+// ts-unused-symbols-ignore-next-line
+export const alsoUsed = 4;
+
+export const reported = 5;
+
+// ts-unused-symbols-ignore-next-line -- kept for the plugin API
+export const withReason = 6;
+
+/* ts-unused-symbols-ignore-next-line */
+export const blockComment = 7;
+
+// ts-unused-symbols-ignore-next-lines
+export const misspelled = 8;
+
+// ts-unused-symbols-ignore-next-line unused-locals -- kept for debugging
+const debugOnly = 9;
+
+// ts-unused-symbols-ignore-next-line unused-exports
+export const targetedUsed = 10;
+
+// ts-unused-symbols-ignore-next-line unused-locals
+export const wrongTarget = 11;
+
+// ts-unused-symbols-ignore-next-line
+
+export const tooFar = 12;
@@ -0,0 +1,2 @@
+/* ts-unused-symbols-disable */
+export const imported = 1;
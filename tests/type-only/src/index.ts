@@ -0,0 +1,11 @@
+import type { User } from "./models";
+import { Order, Settings, createOrder } from "./models";
+import { type Product } from "./models";
+import { Product as P } from "./models";
+
+const user: User = { name: "" };
+const order: Order = createOrder();
+const settings: Settings = { debug: false };
+const products: Product[] = [new P()];
+
+console.log(user, order, settings, products);
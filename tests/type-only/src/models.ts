@@ -0,0 +1,19 @@
+export class User {
+  name = "";
+}
+
+export class Order {
+  id = 0;
+}
+
+export class Product {
+  sku = "";
+}
+
+export interface Settings {
+  debug: boolean;
+}
+
+export function createOrder(): Order {
+  return new Order();
+}
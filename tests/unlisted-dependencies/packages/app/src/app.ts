@@ -0,0 +1,14 @@
+import React from "react";
+import { debounce } from "lodash";
+import { readFileSync } from "fs";
+export { value } from "lib";
+import type { Request } from "express";
+
+const dayjs = await import("dayjs");
+const { v4 } = require("uuid");
+const ReactDom = await import("react");
+const plugin = require(process.env.PLUGIN!);
+
+console.log(React, debounce, readFileSync, dayjs, v4, ReactDom, plugin);
+
+export function handle(request: Request) {}
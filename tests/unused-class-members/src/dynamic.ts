@@ -0,0 +1,7 @@
+export class Dynamic {
+  unused() {}
+
+  call(key: "unused") {
+    return this[key]();
+  }
+}
@@ -0,0 +1,7 @@
+import { Service } from "./service";
+
+const service = Service.create();
+const { value } = service;
+
+service?.usedMethod();
+console.log(value);
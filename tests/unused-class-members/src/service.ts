@@ -0,0 +1,35 @@
+export class Service {
+  static create() {
+    return new Service("service");
+  }
+
+  unusedMethod() {}
+
+  usedMethod() {
+    return this.helper();
+  }
+
+  private helper() {
+    return this.count;
+  }
+
+  count = 0;
+
+  unusedProperty = 1;
+
+  get value() {
+    return this.count;
+  }
+
+  #secret = 2;
+
+  static {
+    Service.create();
+  }
+
+  constructor(readonly name: string) {}
+}
+
+export class Component extends Base {
+  render() {}
+}
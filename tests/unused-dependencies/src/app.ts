@@ -0,0 +1,4 @@
+import { debounce } from "lodash";
+import React from "react";
+
+console.log(debounce, React);
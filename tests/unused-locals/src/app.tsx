@@ -0,0 +1,5 @@
+const Button = () => null;
+
+const title = "app";
+
+export const App = () => <Button title={title} />;
@@ -0,0 +1,38 @@
+import { used } from "./used";
+
+const unusedConstant = 1;
+
+function helper() {
+  return 2;
+}
+
+function onlyUsedByUnusedExport() {
+  return helper();
+}
+
+export function unusedExport() {
+  return onlyUsedByUnusedExport();
+}
+
+function usedBySideEffect() {}
+
+usedBySideEffect();
+
+interface Options {
+  value: number;
+}
+
+export function usedExport(options: Options) {
+  return used(options.value);
+}
+
+function recursive(n: number): number {
+  return n ? recursive(n - 1) : 0;
+}
+
+const shadowed = 1;
+
+export function shadowing() {
+  const shadowed = 2;
+  return shadowed;
+}
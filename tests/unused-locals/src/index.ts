@@ -0,0 +1,6 @@
+import { App } from "./app";
+import { shadowing, usedExport } from "./helpers";
+import { patterns } from "./patterns";
+
+App();
+usedExport({ value: shadowing() + patterns(1) });
@@ -0,0 +1,8 @@
+const [first, second] = [1, 2];
+const { kept, ...rest } = { kept: 1, dropped: 2 };
+
+type Alias = number;
+
+export function patterns(value: Alias) {
+  return first + kept + value;
+}
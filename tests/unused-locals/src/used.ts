@@ -0,0 +1 @@
+export const used = (value: number) => value;
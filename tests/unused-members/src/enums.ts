@@ -0,0 +1,24 @@
+export enum Color {
+  Red,
+  Green,
+  Blue,
+}
+
+export enum Status {
+  Active = "active",
+  Inactive = "inactive",
+}
+
+export enum Direction {
+  Up,
+  Down,
+}
+
+export const Sizes = {
+  small: 1,
+  large: 2,
+} as const;
+
+export function isActive(status: Status) {
+  return status === Status.Active;
+}
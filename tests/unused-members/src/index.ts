@@ -0,0 +1,5 @@
+import { Color, Direction, Sizes, isActive } from "./enums";
+import * as Enums from "./enums";
+
+console.log(Color.Red, Enums.Color.Green, Sizes.small, isActive);
+console.log(Object.values(Direction));